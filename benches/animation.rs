@@ -0,0 +1,111 @@
+//! Compare the ways animation nodes are evaluated.
+//!
+//! Run with `cargo bench --bench animation`. Each case reports the average
+//! time of one frame after a short warm up.
+
+use bevy_app::{App, TaskPoolPlugin};
+use bevy_ecs::prelude::*;
+use bevy_keyframe::{
+    AnimationDuration, AnimationOf, AnimationTarget, Delta, KeyframePlugin, ObserverDriven,
+    drivers::{PlaybackMode, RepeatMode, TimeDriver},
+    lens,
+};
+use bevy_math::Vec3;
+use bevy_time::{TimePlugin, TimeUpdateStrategy};
+use bevy_transform::components::Transform;
+use std::time::{Duration, Instant};
+
+const WARM_UP: u32 = 10;
+const FRAMES: u32 = 200;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((TaskPoolPlugin::default(), TimePlugin, KeyframePlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            1.0 / 60.0,
+        )));
+    app
+}
+
+/// Spawn a delta animation on `target`, looping if `playing`.
+fn spawn_delta(world: &mut World, target: Entity, playing: bool, observed: bool) {
+    let mut node = world.spawn((Delta(Vec3::X), AnimationDuration::secs(1.0)));
+    if observed {
+        node.insert(ObserverDriven);
+    }
+    let node = node.id();
+
+    let mut root = world.spawn((AnimationTarget(target), lens!(Transform::translation)));
+    if playing {
+        root.insert(TimeDriver::default().with_mode(PlaybackMode::Repeat(RepeatMode::PingPong)));
+    }
+    root.add_related::<AnimationOf>(&[node]);
+}
+
+fn bench(name: &str, mut app: App) {
+    for _ in 0..WARM_UP {
+        app.update();
+    }
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    let frame = start.elapsed() / FRAMES;
+
+    println!("{name:<48} {frame:>12.2?} / frame");
+}
+
+/// 10,000 playing nodes, either each with its own target or all sharing one.
+///
+/// Nodes are evaluated in parallel across targets, so the shared
+/// target shows the cost of evaluating the same nodes serially.
+fn throughput() {
+    const NODES: usize = 10_000;
+
+    for (name, shared) in [
+        ("10k nodes, 10k targets (parallel)", false),
+        ("10k nodes, 1 target (serial)", true),
+    ] {
+        let mut app = app();
+        let world = app.world_mut();
+        let shared_target = world.spawn(Transform::default()).id();
+        for _ in 0..NODES {
+            let target = match shared {
+                true => shared_target,
+                false => world.spawn(Transform::default()).id(),
+            };
+            spawn_delta(world, target, true, false);
+        }
+
+        bench(name, app);
+    }
+}
+
+/// A handful of playing nodes among 10,000 idle ones.
+///
+/// Polling scans every node for movement each stage,
+/// while observers only run for the nodes that moved.
+fn observers() {
+    const IDLE: usize = 10_000;
+    const PLAYING: usize = 8;
+
+    for (name, observed) in [
+        ("8 of 10k nodes playing, polled", false),
+        ("8 of 10k nodes playing, observed", true),
+    ] {
+        let mut app = app();
+        let world = app.world_mut();
+        for i in 0..IDLE + PLAYING {
+            let target = world.spawn(Transform::default()).id();
+            spawn_delta(world, target, i < PLAYING, observed);
+        }
+
+        bench(name, app);
+    }
+}
+
+fn main() {
+    throughput();
+    observers();
+}
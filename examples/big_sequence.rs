@@ -1,5 +1,14 @@
 use bevy::prelude::*;
 
+/// How far inside a building the player is, from `0.0` to `1.0`.
+#[derive(Resource, Default)]
+struct InsideAmount(f32);
+
+/// Map the inside amount onto the half-second modifier sequence below.
+fn inside_amount(inside: Res<InsideAmount>) -> f32 {
+    inside.0 * 0.5
+}
+
 fn animations(mut commands: Commands) {
     commands.spawn((
         VolumeNode::default(),
@@ -48,7 +57,7 @@ fn animations(mut commands: Commands) {
                         // Here's a neested combinator that'll run all three leaves at once.
                         animations![
                             // A simple entity like this facilitates delays.
-                            AnimationDuration::secs(0.5),
+                            Delay::secs(0.5),
                             // You can also emit arbitrary events at specific points in the animtion.
                             // AnimationEvent(MyEvent),
                             // Or run arbitrary one-shot systems.
@@ -62,16 +71,11 @@ fn animations(mut commands: Commands) {
                     ],
                 ),
                 (
-                    // An animation can simply be a single leaf node.
-                    //
-                    // Components like `AnimationEvent` have `Animation::Leaf` as a required component,
-                    // so this also doesn't generally need to be specified.
-                    Animation::Leaf,
                     // We can drive the animation playhead with arbitrary clocks,
                     // like the playhead of a sample.
                     SampleRunner,
                     animations![
-                        AnimationDuration(Duration::from_secs_f32(0.5)),
+                        Delay::secs(0.5),
                         // At exactly half a second into a piece of music, we'll trigger some
                         // behavior. Note that since we're following the sample's playhead,
                         // this will respect any pausing, changes in speed, or even reversed
@@ -80,24 +84,18 @@ fn animations(mut commands: Commands) {
                     ]
                 ),
                 (
-                    // // A runner like this doesn't have a concept of "start"
-                    // // or "end," since it's an arbitrary parameter calculated from the world,
-                    // // like how "inside-of-a-building" the player is.
-                    // InsideAmountRunner,
+                    // A driver like this doesn't have a concept of "start"
+                    // or "end," since it's an arbitrary parameter calculated from the world,
+                    // like how "inside-of-a-building" the player is.
+                    ParamDriver::new(inside_amount),
                     // Instead of setting the values directly, we can also
                     // multiply their "base" values. The "base" value is
                     // determined by the blended animations or the initial
                     // value of the animation target if no other animations
                     // are present.
                     animations![
-                        Modifier {
-                            value: 0.75,
-                            position: Duration::ZERO,
-                        },
-                        Modifier {
-                            value: 1.0,
-                            position: Duration::from_secs_f32(0.5),
-                        },
+                        Modifier(0.75),
+                        (Modifier(1.0), AnimationDuration::secs(0.5)),
                     ],
                 ),
             ],
@@ -121,16 +119,13 @@ fn animations(mut commands: Commands) {
 
     // The above is quite involved, but it doesn't have to be.
     // Simple animations are simple to spawn.
-    fn fade_in(seconds: f32) -> impl Bundle {
-        (
-            drivers::TimeDriver::default(),
+    fn fade_in(entity: &mut EntityCommands, seconds: f32) {
+        entity.animate(
+            AnimationDuration::secs(seconds),
             lens!(VolumeNode::volume),
-            animations![(
-                Keyframe(Volume::Decibels(0.0)),
-                AnimationDuration(Duration::from_secs_f32(seconds)),
-            )],
-        )
+            Keyframe(Volume::Decibels(0.0)),
+        );
     }
 
-    commands.spawn((VolumeNode::default(), fade_in(1.5)));
+    fade_in(&mut commands.spawn(VolumeNode::default()), 1.5);
 }
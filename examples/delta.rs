@@ -1,18 +1,44 @@
-use bevy::prelude::*;
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+};
 use bevy_color::palettes::css::GREEN;
 use bevy_keyframe::{drivers::TimeDriver, *};
 use drivers::{PlaybackMode, RepeatMode};
 use std::f32::consts::FRAC_PI_2;
 
+/// The number of square pairs to spawn.
+///
+/// Set `SQUARE_PAIRS` to stress test many animations at
+/// once, like `SQUARE_PAIRS=5000 cargo run --release --example delta`.
+#[derive(Resource)]
+struct SquarePairs(usize);
+
 fn main() {
-    App::new()
-        .add_plugins((DefaultPlugins, KeyframePlugin))
+    let pairs = std::env::var("SQUARE_PAIRS")
+        .ok()
+        .and_then(|pairs| pairs.parse().ok())
+        .unwrap_or(1);
+
+    let mut app = App::new();
+    app.add_plugins((DefaultPlugins, KeyframePlugin))
+        .insert_resource(SquarePairs(pairs))
         .add_systems(Startup, startup)
-        .add_systems(Update, watch_tester)
-        .run();
+        .add_systems(Update, watch_tester);
+
+    // Report frame times so runs with different counts can be compared.
+    if pairs > 1 {
+        app.add_plugins((
+            FrameTimeDiagnosticsPlugin::default(),
+            LogDiagnosticsPlugin::default(),
+        ));
+    }
+
+    app.run();
 }
 
 fn startup(
+    pairs: Res<SquarePairs>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
@@ -27,22 +53,21 @@ fn startup(
     let duration = 1.0;
     let hscale = scale / 2.0;
 
-    let i = 0usize;
-    // for i in 0..10000 {
-    commands.spawn((
-        Mesh2d(square.clone()),
-        MeshMaterial2d(square_material.clone()),
-        Transform::from_xyz(-scale * 1.5 + i as f32, -hscale, 0.0),
-        trace_square(scale, duration),
-    ));
+    for i in 0..pairs.0 {
+        commands.spawn((
+            Mesh2d(square.clone()),
+            MeshMaterial2d(square_material.clone()),
+            Transform::from_xyz(-scale * 1.5 + i as f32, -hscale, 0.0),
+            trace_square(scale, duration),
+        ));
 
-    commands.spawn((
-        Mesh2d(square.clone()),
-        MeshMaterial2d(square_material.clone()),
-        Transform::from_xyz(hscale + i as f32, -hscale, 0.0),
-        trace_square(scale, duration),
-    ));
-    // }
+        commands.spawn((
+            Mesh2d(square.clone()),
+            MeshMaterial2d(square_material.clone()),
+            Transform::from_xyz(hscale + i as f32, -hscale, 0.0),
+            trace_square(scale, duration),
+        ));
+    }
 }
 
 #[derive(Component)]
@@ -55,30 +80,31 @@ fn watch_tester(q: Query<Entity, With<Tester>>, mut commands: Commands) {
 }
 
 fn trace_square(scale: f32, duration: f32) -> impl Bundle {
-    let duration_and_curve = (
-        AnimationDuration::secs(duration),
-        AnimationCurve(EaseFunction::CubicInOut),
-    );
+    // The baseline keeps the squares from drifting over long runs.
+    let duration_and_curve = || {
+        (
+            AnimationDuration::secs(duration),
+            AnimationCurve::Ease(EaseFunction::CubicInOut),
+            DeltaBaseline,
+        )
+    };
 
     (
         // Because these lenses don't conflict, we can stuff both
         // of them in the same sequence.
         lens!(Transform::translation),
         lens!(Transform::rotation),
-        TimeDriver {
-            mode: PlaybackMode::Repeat(RepeatMode::PingPong),
-            ..Default::default()
-        },
+        TimeDriver::default().with_mode(PlaybackMode::Repeat(RepeatMode::PingPong)),
         animations![
-            (duration_and_curve, Delta(Vec3::X * scale)),
+            (duration_and_curve(), Delta(Vec3::X * scale)),
             (
-                duration_and_curve,
+                duration_and_curve(),
                 Delta(Vec3::Y * scale),
                 Delta(Quat::from_rotation_z(FRAC_PI_2)),
             ),
-            (duration_and_curve, Delta(Vec3::NEG_X * scale)),
+            (duration_and_curve(), Delta(Vec3::NEG_X * scale)),
             (
-                duration_and_curve,
+                duration_and_curve(),
                 Delta(Vec3::NEG_Y * scale),
                 Delta(Quat::from_rotation_z(-FRAC_PI_2)),
             ),
@@ -0,0 +1,60 @@
+//! Animating a `TimeDriver`'s own speed for a slow-motion ramp.
+
+use bevy::prelude::*;
+use bevy_color::palettes::css::GREEN;
+use bevy_keyframe::{drivers::TimeDriver, *};
+use drivers::{PlaybackMode, RepeatMode};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, KeyframePlugin))
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn(Camera2d);
+
+    let square = commands
+        .spawn((
+            Mesh2d(meshes.add(Rectangle::new(50.0, 50.0))),
+            MeshMaterial2d(materials.add(Color::from(GREEN))),
+            Transform::from_xyz(-200.0, 0.0, 0.0),
+            lens!(Transform::translation),
+            TimeDriver::default().with_mode(PlaybackMode::Repeat(RepeatMode::Restart)),
+            animations![
+                (
+                    Keyframe(Vec3::new(200.0, 0.0, 0.0)),
+                    AnimationDuration::secs(1.0),
+                    AnimationCurve::Ease(EaseFunction::SineInOut),
+                ),
+                (
+                    Keyframe(Vec3::new(-200.0, 0.0, 0.0)),
+                    AnimationDuration::secs(1.0),
+                    AnimationCurve::Ease(EaseFunction::SineInOut),
+                ),
+            ],
+        ))
+        .id();
+
+    // A second animation ramps the square's playback speed down to 0.2
+    // and back. Speed changes take effect on the next frame. The square
+    // restarts rather than ping-ponging, since reversing negates its speed.
+    commands.spawn((
+        lens!(square => TimeDriver::speed),
+        TimeDriver::default().with_mode(PlaybackMode::Repeat(RepeatMode::PingPong)),
+        animations![
+            AnimationDuration::secs(1.5),
+            (
+                Keyframe(0.2f32),
+                AnimationDuration::secs(1.0),
+                AnimationCurve::Ease(EaseFunction::CubicInOut),
+            ),
+            AnimationDuration::secs(1.5),
+        ],
+    ));
+}
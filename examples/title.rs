@@ -19,7 +19,7 @@ fn startup(mut commands: Commands) {
     commands.spawn((
         Text2d::new("Bevy Keyframe"),
         TextFont {
-            font_size,
+            font_size: font_size * 0.75,
             ..Default::default()
         },
         TextColor(start_color),
@@ -32,14 +32,18 @@ fn title_shift(initial_delay: f32, target_color: Color, font_size: f32) -> impl
     (
         lens!(Transform::translation),
         lens!(TextColor::0),
+        // `TextFont` is re-laid out whenever it changes,
+        // so its size can be animated like any other field.
+        lens!(TextFont::font_size),
         TimeDriver::default(),
         animations![
             AnimationDuration::secs(initial_delay),
             (
                 Keyframe(Vec3::new(0.0, 100.0, 0.0)),
                 Keyframe(target_color),
+                Keyframe(font_size),
                 AnimationDuration::secs(1.3),
-                AnimationCurve(EaseFunction::QuarticInOut),
+                AnimationCurve::Ease(EaseFunction::QuarticInOut),
             ),
             AnimationCallback::new(move |mut commands: Commands| {
                 commands.spawn((Transform::from_xyz(0.0, 100.0, -1.0), shadow(font_size)));
@@ -58,10 +62,12 @@ fn shadow(font_size: f32) -> impl Bundle {
         let dist = i as f32 * 5.0;
         let z = -i as f32;
 
-        let curve_and_duration = (
-            AnimationCurve(EaseFunction::CubicInOut),
-            AnimationDuration::secs(0.75),
-        );
+        let curve_and_duration = || {
+            (
+                AnimationCurve::Ease(EaseFunction::CubicInOut),
+                AnimationDuration::secs(0.75),
+            )
+        };
 
         (
             Text2d::new("Bevy Keyframe"),
@@ -72,15 +78,13 @@ fn shadow(font_size: f32) -> impl Bundle {
             TextColor(color),
             Transform::from_xyz(0.0, 0.0, z),
             lens!(Transform::translation),
-            TimeDriver {
-                mode: drivers::PlaybackMode::Repeat(drivers::RepeatMode::Restart),
-                ..Default::default()
-            },
+            TimeDriver::default()
+                .with_mode(drivers::PlaybackMode::Repeat(drivers::RepeatMode::Restart)),
             animations![
-                (curve_and_duration, Keyframe(Vec3::new(-dist, dist, z))),
-                (curve_and_duration, Keyframe(Vec3::new(0.0, 0.0, z))),
-                (curve_and_duration, Keyframe(Vec3::new(dist, -dist, z))),
-                (curve_and_duration, Keyframe(Vec3::new(0.0, 0.0, z))),
+                (curve_and_duration(), Keyframe(Vec3::new(-dist, dist, z))),
+                (curve_and_duration(), Keyframe(Vec3::new(0.0, 0.0, z))),
+                (curve_and_duration(), Keyframe(Vec3::new(dist, -dist, z))),
+                (curve_and_duration(), Keyframe(Vec3::new(0.0, 0.0, z))),
             ],
         )
     });
@@ -0,0 +1,107 @@
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{Data, DeriveInput, Member, parse_macro_input, spanned::Spanned};
+
+/// Derive `AnimationLerp` for structs whose fields all implement `AnimationLerp`.
+///
+/// Each method is applied field-wise.
+#[proc_macro_derive(AnimationLerp)]
+pub fn derive_animation_lerp(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn derive(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "`AnimationLerp` cannot be derived for enums",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "`AnimationLerp` cannot be derived for unions",
+            ));
+        }
+    };
+
+    let lerp = quote! { ::bevy_keyframe::AnimationLerp };
+
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(i.into()),
+            };
+
+            (member, field.ty.span())
+        })
+        .collect();
+
+    let identity_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #m: #lerp::identity() }
+    });
+    let lerp_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #m: #lerp::animation_lerp(&self.#m, &other.#m, amount) }
+    });
+    let lerp_into_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #lerp::animation_lerp_into(&self.#m, &other.#m, amount, &mut out.#m); }
+    });
+    let difference_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #m: #lerp::difference(&self.#m, &other.#m) }
+    });
+    let accumulate_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #lerp::accumulate(&mut self.#m, &value.#m); }
+    });
+    let scale_fields = members.iter().map(|(m, span)| {
+        quote_spanned! {*span=> #m: #lerp::scale(&self.#m, factor) }
+    });
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(syn::parse_quote_spanned! {ty.span()=> #ty: #lerp });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #lerp for #name #ty_generics #where_clause {
+            fn identity() -> Self {
+                Self { #(#identity_fields,)* }
+            }
+
+            fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+                Self { #(#lerp_fields,)* }
+            }
+
+            fn animation_lerp_into(&self, other: &Self, amount: f32, out: &mut Self) {
+                #(#lerp_into_fields)*
+            }
+
+            fn difference(&self, other: &Self) -> Self {
+                Self { #(#difference_fields,)* }
+            }
+
+            fn accumulate(&mut self, value: &Self) {
+                #(#accumulate_fields)*
+            }
+
+            fn scale(&self, factor: f32) -> Self {
+                Self { #(#scale_fields,)* }
+            }
+        }
+    })
+}
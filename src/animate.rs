@@ -0,0 +1,75 @@
+use super::{
+    AnimationComplete, AnimationDuration, AnimationTarget, DynamicFieldLens, animations,
+    drivers::TimeDriver, lerp::AnimationLerp,
+};
+use bevy_ecs::prelude::*;
+
+/// Commands for spawning one-shot animations.
+pub trait AnimateCommands {
+    /// Spawn a single animation node that targets this entity through `lens`.
+    ///
+    /// The animation lives on its own entity, which is despawned
+    /// once it completes, so the target is left untouched.
+    ///
+    /// ```ignore
+    /// commands.entity(node).animate(
+    ///     AnimationDuration::secs(1.5),
+    ///     lens!(VolumeNode::volume),
+    ///     Keyframe(Volume::Decibels(0.0)),
+    /// );
+    /// ```
+    fn animate<T: AnimationLerp>(
+        &mut self,
+        duration: impl Into<AnimationDuration>,
+        lens: impl Into<DynamicFieldLens<T>>,
+        node: impl Bundle,
+    ) -> &mut Self;
+}
+
+impl AnimateCommands for EntityCommands<'_> {
+    fn animate<T: AnimationLerp>(
+        &mut self,
+        duration: impl Into<AnimationDuration>,
+        lens: impl Into<DynamicFieldLens<T>>,
+        node: impl Bundle,
+    ) -> &mut Self {
+        let target = self.id();
+        self.commands().spawn((
+            TimeDriver::default(),
+            AnimationTarget(target),
+            AnimationComplete::Despawn,
+            lens.into(),
+            animations![(node, duration.into())],
+        ));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keyframe, lens, testing::app};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn animate_reaches_its_end_value() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        app.world_mut().commands().entity(target).animate(
+            AnimationDuration::secs(0.45),
+            lens!(Transform::translation),
+            Keyframe(Vec3::X),
+        );
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let world = app.world_mut();
+        assert_eq!(world.get::<Transform>(target).unwrap().translation, Vec3::X);
+        let mut drivers = world.query::<&TimeDriver>();
+        assert_eq!(drivers.iter(world).count(), 0);
+    }
+}
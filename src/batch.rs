@@ -0,0 +1,156 @@
+use super::{
+    blend::{BufferedWrite, FieldWriter},
+    lens::{FieldAccess, FieldGetter},
+    lerp::AnimationLerp,
+};
+use bevy_ecs::{entity::index_map::EntityIndexMap, prelude::*};
+use bevy_tasks::{ComputeTaskPool, ParallelSliceMut, TaskPool};
+use core::cell::RefCell;
+
+/// Evaluate animation nodes in parallel across their targets.
+///
+/// Each item pairs a node with the entity it targets. Nodes that share a
+/// target are evaluated in order on the same thread, so they observe each
+/// other's writes just like serial evaluation, while different targets are
+/// evaluated in parallel. Values that need blending are buffered once every
+/// target is done.
+///
+/// If any node fails, the others still apply and the first error is returned.
+pub(crate) fn apply_by_target<T, N>(
+    nodes: impl IntoIterator<Item = (Entity, N)>,
+    targets: &mut Query<FieldGetter<T>>,
+    writer: &mut FieldWriter<T>,
+    apply: impl Fn(&N, FieldAccess<T>, &FieldWriter<T>) -> Result<Option<BufferedWrite<T>>>
+    + Send
+    + Sync,
+) -> Result
+where
+    T: AnimationLerp,
+    N: Sync,
+{
+    let mut first_error = None;
+    let mut groups = EntityIndexMap::<Vec<N>>::default();
+    for (target, node) in nodes {
+        let group = match groups.get_mut(&target) {
+            Some(group) => group,
+            None => {
+                // Report missing targets like a serial lookup would.
+                if let Err(e) = targets.get(target) {
+                    first_error.get_or_insert(e.into());
+                    continue;
+                }
+                groups.entry(target).or_default()
+            }
+        };
+        group.push(node);
+    }
+
+    if groups.is_empty() {
+        return first_error.map_or(Ok(()), Err);
+    }
+
+    // `Query::par_iter_many_unique_mut` checks the uniqueness of its list
+    // pairwise, which is quadratic in the number of targets, so we fetch the
+    // targets serially and split them across threads ourselves.
+    let mut entities: Vec<_> = targets
+        .reborrow()
+        .iter_many_unique_inner(groups.keys())
+        .collect();
+
+    let shared = &*writer;
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    let chunks = entities.par_splat_map_mut(pool, None, |_, entities| {
+        let mut buffered = Vec::new();
+        let mut error = None;
+        for entity in entities {
+            let Some(nodes) = groups.get(&entity.id()) else {
+                continue;
+            };
+
+            for node in nodes {
+                match apply(node, FieldAccess::new(entity.reborrow()), shared) {
+                    Ok(Some(write)) => buffered.push(write),
+                    Ok(None) => {}
+                    Err(e) => {
+                        error.get_or_insert(e);
+                    }
+                }
+            }
+        }
+
+        (buffered, error)
+    });
+
+    for (buffered, error) in chunks {
+        for write in buffered {
+            writer.buffer(write);
+        }
+        first_error = first_error.or(error);
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Queues the commands a node issues while it's evaluated.
+///
+/// Systems evaluate nodes in parallel, so they use [`ParallelCommands`].
+/// Observers never apply a [`ParallelCommands`] buffer, so they use their
+/// own [`Commands`] instead.
+pub(crate) trait NodeCommands {
+    fn command_scope(&self, f: impl FnOnce(Commands));
+}
+
+impl NodeCommands for ParallelCommands<'_, '_> {
+    fn command_scope(&self, f: impl FnOnce(Commands)) {
+        ParallelCommands::command_scope(self, f);
+    }
+}
+
+impl NodeCommands for RefCell<Commands<'_, '_>> {
+    fn command_scope(&self, f: impl FnOnce(Commands)) {
+        f(self.borrow_mut().reborrow());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blend::BlendBuffer, lens::register_node_components, testing::app};
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_transform::components::Transform;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn missing_targets_dont_stop_the_rest() {
+        let mut app = app(0.1);
+        let world = app.world_mut();
+        register_node_components::<f32>(world);
+        world.init_resource::<BlendBuffer<f32>>();
+
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+        let target = world.spawn(Transform::default()).id();
+
+        let (result, applied) = world
+            .run_system_once(
+                move |mut targets: Query<FieldGetter<f32>>, mut writer: FieldWriter<f32>| {
+                    let applied = AtomicUsize::new(0);
+                    let result = apply_by_target(
+                        [(missing, ()), (target, ())],
+                        &mut targets,
+                        &mut writer,
+                        |_, _, _| {
+                            applied.fetch_add(1, Ordering::Relaxed);
+                            Ok(None)
+                        },
+                    );
+
+                    (result, applied.into_inner())
+                },
+            )
+            .unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(applied, 1);
+    }
+}
@@ -0,0 +1,312 @@
+use crate::{
+    AnimationOf, DynamicFieldLens, FieldLens,
+    crossfade::{CrossFade, CrossFadeWeight, FadeSide},
+    lens::{FieldAccess, FieldGetter},
+    lerp::AnimationLerp,
+    report_cycle,
+};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_log::warn;
+use bevy_platform::collections::{HashMap, HashSet};
+use std::any::TypeId;
+
+/// Identifies a field on a component.
+///
+/// This is typically constructed with the [`blend_field!`][crate::blend_field] macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldId {
+    component: TypeId,
+    field: &'static str,
+}
+
+impl FieldId {
+    pub fn of<C: Component>(field: &'static str) -> Self {
+        Self {
+            component: TypeId::of::<C>(),
+            field,
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! blend_field {
+    ($component:ident::$field:tt $(.$rest:tt)*) => {
+        $crate::FieldId::of::<$component>(concat!(stringify!($field) $(, ".", stringify!($rest))*))
+    };
+}
+
+/// How the values of concurrently-active animations are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    #[default]
+    Mean,
+    Min,
+    Max,
+    Sum,
+    Product,
+}
+
+/// Per-field blending configuration.
+///
+/// When several animations write to the same field of the same entity,
+/// fields with a [`BlendMode`] have their values combined once all
+/// animations have been evaluated. Otherwise, the last writer wins.
+///
+/// Only fields animated through lenses with a [`FieldId`], like
+/// those created with [`lens!`][crate::lens], can be blended.
+#[derive(Resource, Debug, Default)]
+pub struct Blending(HashMap<FieldId, BlendMode>);
+
+impl Blending {
+    pub fn insert(&mut self, field: FieldId, mode: BlendMode) -> &mut Self {
+        self.0.insert(field, mode);
+        self
+    }
+
+    pub fn remove(&mut self, field: FieldId) -> Option<BlendMode> {
+        self.0.remove(&field)
+    }
+
+    pub fn get(&self, field: FieldId) -> Option<BlendMode> {
+        self.0.get(&field).copied()
+    }
+}
+
+/// Values waiting to be blended this frame.
+#[derive(Resource)]
+pub struct BlendBuffer<T: AnimationLerp>(HashMap<(Entity, FieldId), BlendEntry<T>>);
+
+impl<T: AnimationLerp> Default for BlendBuffer<T> {
+    fn default() -> Self {
+        Self(HashMap::default())
+    }
+}
+
+/// The values written to a single field this frame.
+struct BlendEntry<T: AnimationLerp> {
+    lens: DynamicFieldLens<T>,
+    values: Vec<T>,
+    /// Values written from within a [`CrossFade`], tagged with the fade and side.
+    faded: Vec<(Entity, FadeSide, T)>,
+}
+
+/// A value that must be blended with the field's other values before it's written.
+pub(crate) struct BufferedWrite<T: AnimationLerp> {
+    lens: DynamicFieldLens<T>,
+    target: Entity,
+    field: FieldId,
+    fade: Option<(Entity, FadeSide)>,
+    value: T,
+}
+
+/// Writes animated values to their targets, deferring to the
+/// [`BlendBuffer`] for fields with a [`BlendMode`] or fields
+/// written from within a [`CrossFade`].
+#[derive(SystemParam)]
+pub struct FieldWriter<'w, 's, T: AnimationLerp> {
+    blending: Res<'w, Blending>,
+    buffer: ResMut<'w, BlendBuffer<T>>,
+    parents: Query<'w, 's, &'static AnimationOf>,
+    fades: Query<'w, 's, &'static CrossFade>,
+}
+
+impl<T: AnimationLerp> FieldWriter<'_, '_, T> {
+    /// Write `value` to the field of `target` produced by the animation node `node`.
+    pub fn write(
+        &mut self,
+        lens: &DynamicFieldLens<T>,
+        node: Entity,
+        target: Entity,
+        entity: FieldAccess<T>,
+        value: T,
+    ) -> Result {
+        if let Some(write) = self.try_write(lens, node, target, entity, value)? {
+            self.buffer(write);
+        }
+
+        Ok(())
+    }
+
+    /// Write `value` directly if it doesn't need to be blended,
+    /// otherwise returning it for [`FieldWriter::buffer`].
+    ///
+    /// This only needs shared access, so nodes animating
+    /// different targets can be written in parallel.
+    pub(crate) fn try_write(
+        &self,
+        lens: &DynamicFieldLens<T>,
+        node: Entity,
+        target: Entity,
+        entity: FieldAccess<T>,
+        value: T,
+    ) -> Result<Option<BufferedWrite<T>>> {
+        let Some((field, fade)) = self.blended_field(lens, node) else {
+            lens.set_field(entity, value)?;
+            return Ok(None);
+        };
+
+        Ok(Some(BufferedWrite {
+            lens: lens.clone(),
+            target,
+            field,
+            fade,
+            value,
+        }))
+    }
+
+    /// Like [`FieldWriter::try_write`], but produces the value by modifying the field.
+    ///
+    /// Fields that don't need to be blended are modified in place, so large
+    /// values can reuse their allocations. Otherwise, a copy of the field is
+    /// modified and returned for [`FieldWriter::buffer`].
+    pub(crate) fn try_modify(
+        &self,
+        lens: &DynamicFieldLens<T>,
+        node: Entity,
+        target: Entity,
+        entity: FieldAccess<T>,
+        mut modify: impl FnMut(&mut T),
+    ) -> Result<Option<BufferedWrite<T>>> {
+        let Some((field, fade)) = self.blended_field(lens, node) else {
+            lens.modify_field(entity, &mut modify)?;
+            return Ok(None);
+        };
+
+        let mut value = lens.get_field(entity)?;
+        modify(&mut value);
+
+        Ok(Some(BufferedWrite {
+            lens: lens.clone(),
+            target,
+            field,
+            fade,
+            value,
+        }))
+    }
+
+    /// The field `node` writes through `lens` and its [`CrossFade`] side,
+    /// if the write must be blended.
+    fn blended_field(
+        &self,
+        lens: &DynamicFieldLens<T>,
+        node: Entity,
+    ) -> Option<(FieldId, Option<(Entity, FadeSide)>)> {
+        let field = lens.field()?;
+        let fade = self.fade_side(node);
+        (fade.is_some() || self.blending.0.contains_key(&field)).then_some((field, fade))
+    }
+
+    /// Queue a write returned by [`FieldWriter::try_write`] to be blended.
+    pub(crate) fn buffer(&mut self, write: BufferedWrite<T>) {
+        let BufferedWrite {
+            lens,
+            target,
+            field,
+            fade,
+            value,
+        } = write;
+
+        let entry = self
+            .buffer
+            .0
+            .entry((target, field))
+            .or_insert_with(|| BlendEntry {
+                lens,
+                values: Vec::new(),
+                faded: Vec::new(),
+            });
+
+        match fade {
+            Some((fade, side)) => entry.faded.push((fade, side, value)),
+            None => entry.values.push(value),
+        }
+    }
+
+    /// Find the nearest [`CrossFade`] above `node` and the side it's on.
+    fn fade_side(&self, node: Entity) -> Option<(Entity, FadeSide)> {
+        if self.fades.is_empty() {
+            return None;
+        }
+
+        let mut child = node;
+        let mut visited = HashSet::new();
+        while let Ok(parent) = self.parents.get(child) {
+            let parent = parent.0;
+            if let Ok(fade) = self.fades.get(parent) {
+                return fade.side(child).map(|side| (parent, side));
+            }
+            if !visited.insert(parent) {
+                report_cycle(parent);
+                break;
+            }
+            child = parent;
+        }
+
+        None
+    }
+}
+
+/// Combine values with the field's blend mode, or let the last one win.
+fn combine<T: AnimationLerp>(mut values: Vec<T>, mode: Option<BlendMode>) -> Option<T> {
+    let Some(mode) = mode else {
+        return values.pop();
+    };
+
+    match T::blend(&values, mode) {
+        Some(value) => Some(value),
+        None => {
+            warn!(
+                "blend mode {mode:?} is not supported for {}",
+                core::any::type_name::<T>()
+            );
+
+            values.pop()
+        }
+    }
+}
+
+pub(crate) fn resolve_blends<T: AnimationLerp>(
+    blending: Res<Blending>,
+    mut buffer: ResMut<BlendBuffer<T>>,
+    weights: Query<&CrossFadeWeight>,
+    mut targets: Query<FieldGetter<T>>,
+) -> Result {
+    for ((target, field), entry) in buffer.0.drain() {
+        let mode = blending.get(field);
+        let BlendEntry {
+            lens,
+            mut values,
+            mut faded,
+        } = entry;
+
+        // Each fade's sides are combined separately, then
+        // interpolated by the fade's weight.
+        while let Some(&(fade, _, _)) = faded.first() {
+            let (this, rest) = faded.into_iter().partition(|(f, ..)| *f == fade);
+            faded = rest;
+
+            let (from, to): (Vec<_>, Vec<_>) = this
+                .into_iter()
+                .partition(|(_, side, _)| *side == FadeSide::From);
+            let from = combine(from.into_iter().map(|(.., v)| v).collect(), mode);
+            let to = combine(to.into_iter().map(|(.., v)| v).collect(), mode);
+
+            let weight = weights.get(fade).map(|w| w.0).unwrap_or(0.0);
+            let value = match (from, to) {
+                (Some(from), Some(to)) => from.animation_lerp(&to, weight),
+                (Some(value), None) | (None, Some(value)) => value,
+                (None, None) => continue,
+            };
+
+            values.push(value);
+        }
+
+        let Some(value) = combine(values, mode) else {
+            continue;
+        };
+
+        lens.set_field(FieldAccess::new(targets.get_mut(target)?), value)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use super::{
+    Animation, AnimationDuration, AnimationOf, AnimationTarget, Animations, playhead::SequenceEvent,
+};
+use bevy_ecs::{component::HookContext, prelude::*, spawn::SpawnableList, world::DeferredWorld};
+
+type SpawnNode = Box<dyn FnOnce(&mut World, Entity) + Send + Sync>;
+type AddObserver = Box<dyn FnOnce(&mut World, Entity) + Send + Sync>;
+
+/// Build an animation hierarchy programmatically.
+///
+/// This is an alternative to the [`animations!`](crate::animations) macro
+/// for hierarchies built in loops or behind conditions.
+///
+/// ```ignore
+/// AnimationBuilder::sequence()
+///     .then((Keyframe(Vec3::X), AnimationDuration::secs(0.5)))
+///     .wait(0.25)
+///     .nest(
+///         AnimationBuilder::parallel()
+///             .then((Keyframe(Vec3::Y), AnimationDuration::secs(1.0)))
+///             .then(AnimationEvent(Done)),
+///     )
+///     .build()
+/// ```
+#[derive(Default)]
+pub struct AnimationBuilder {
+    animation: Animation,
+    nodes: AnimationNodeList,
+    observers: SequenceObservers,
+}
+
+impl AnimationBuilder {
+    /// Start a node whose children run one after another.
+    pub fn sequence() -> Self {
+        Self {
+            animation: Animation::Sequence,
+            nodes: AnimationNodeList::default(),
+            observers: SequenceObservers::default(),
+        }
+    }
+
+    /// Start a node whose children all run at once.
+    pub fn parallel() -> Self {
+        Self {
+            animation: Animation::Parallel,
+            nodes: AnimationNodeList::default(),
+            observers: SequenceObservers::default(),
+        }
+    }
+
+    /// Add a child node.
+    pub fn then(mut self, node: impl Bundle) -> Self {
+        self.nodes.0.push(Box::new(move |world, parent| {
+            world.spawn((AnimationOf(parent), node));
+        }));
+        self
+    }
+
+    /// Add a child node that does nothing for some number of seconds.
+    pub fn wait(self, seconds: f32) -> Self {
+        self.then(AnimationDuration::secs(seconds))
+    }
+
+    /// Add a child built by another builder.
+    pub fn nest(self, builder: AnimationBuilder) -> Self {
+        self.then(builder.build())
+    }
+
+    /// Add a child node for each item.
+    pub fn then_each<B: Bundle>(self, nodes: impl IntoIterator<Item = B>) -> Self {
+        nodes.into_iter().fold(self, Self::then)
+    }
+
+    /// Animate `target` instead of the entity this node is spawned on.
+    ///
+    /// Children inherit the target unless they set their own.
+    pub fn target(mut self, target: Entity) -> Self {
+        self.observers.0.push(Box::new(move |world, entity| {
+            if let Ok(mut entity) = world.get_entity_mut(entity) {
+                entity.insert(AnimationTarget(target));
+            }
+        }));
+        self
+    }
+
+    /// Run a system when this node's sequence completes.
+    ///
+    /// [`SequenceEvent`]s are triggered on the entity holding the playhead,
+    /// so this only runs for the node that's driven, typically the root.
+    pub fn on_complete<M>(
+        mut self,
+        system: impl IntoSystem<(), (), M> + Send + Sync + 'static,
+    ) -> Self {
+        self.observers.0.push(Box::new(move |world, entity| {
+            if world.get_entity(entity).is_err() {
+                return;
+            }
+
+            let id = world.register_system(system);
+            world.entity_mut(entity).observe(
+                move |trigger: Trigger<SequenceEvent>, mut commands: Commands| {
+                    if *trigger == SequenceEvent::SequenceCompleted {
+                        commands.run_system(id);
+                    }
+                },
+            );
+        }));
+        self
+    }
+
+    /// Finish the node, producing the same structure as [`animations!`](crate::animations).
+    pub fn build(self) -> impl Bundle {
+        (
+            self.animation,
+            Animations::spawn(self.nodes),
+            self.observers,
+        )
+    }
+}
+
+/// Observers and other setup applied to a node once it's spawned.
+#[derive(Component, Default)]
+#[component(on_add = Self::on_add_hook)]
+struct SequenceObservers(Vec<AddObserver>);
+
+impl SequenceObservers {
+    fn on_add_hook(mut world: DeferredWorld, context: HookContext) {
+        let Some(mut observers) = world.get_mut::<Self>(context.entity) else {
+            return;
+        };
+
+        let observers = core::mem::take(&mut observers.0);
+        if observers.is_empty() {
+            return;
+        }
+
+        world.commands().queue(move |world: &mut World| {
+            for observer in observers {
+                observer(world, context.entity);
+            }
+        });
+    }
+}
+
+/// The children of an [`AnimationBuilder`].
+#[derive(Default)]
+pub struct AnimationNodeList(Vec<SpawnNode>);
+
+impl SpawnableList<AnimationOf> for AnimationNodeList {
+    fn spawn(self, world: &mut World, entity: Entity) {
+        for node in self.0 {
+            node(world, entity);
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+}
@@ -0,0 +1,98 @@
+use super::{Animation, AnimationDuration, AnimationOf, get_time, playhead::PlayheadMove};
+use bevy_ecs::{component::HookContext, prelude::*, world::DeferredWorld};
+use std::time::Duration;
+
+/// Fade between two animation subtrees.
+///
+/// Both subtrees play in parallel, and wherever they animate the same
+/// field of the same target, their values are interpolated by a weight
+/// that ramps from `0.0` to `1.0` over `duration`. Each side's values
+/// are resolved with the field's [`BlendMode`](crate::BlendMode) first,
+/// or the last writer if it has none. Fields written by only one side take
+/// that side's value.
+///
+/// `from` and `to` are moved under this node when it's added. Once the weight
+/// reaches `1.0`, the `from` subtree is despawned, so the fade is one-way.
+///
+/// Only fields animated through lenses with a [`FieldId`](crate::FieldId) can
+/// be faded. Nested fades are resolved by the nearest fade only.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[require(Animation = Animation::Parallel, CrossFadeWeight)]
+#[component(on_add = Self::on_add_hook)]
+pub struct CrossFade {
+    pub from: Entity,
+    pub to: Entity,
+    pub duration: Duration,
+}
+
+/// The current weight of a [`CrossFade`]'s `to` subtree.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct CrossFadeWeight(pub f32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FadeSide {
+    From,
+    To,
+}
+
+/// A leaf spanning the fade, whose movement drives the weight.
+#[derive(Component, Debug)]
+pub(crate) struct CrossFadeRamp;
+
+impl CrossFade {
+    pub fn new(from: Entity, to: Entity, duration: Duration) -> Self {
+        Self { from, to, duration }
+    }
+
+    pub(crate) fn side(&self, child: Entity) -> Option<FadeSide> {
+        if child == self.from {
+            Some(FadeSide::From)
+        } else if child == self.to {
+            Some(FadeSide::To)
+        } else {
+            None
+        }
+    }
+
+    fn on_add_hook(mut world: DeferredWorld, context: HookContext) {
+        let Some(fade) = world.get::<Self>(context.entity).copied() else {
+            return;
+        };
+
+        let mut commands = world.commands();
+        commands
+            .entity(fade.from)
+            .insert(AnimationOf(context.entity));
+        commands.entity(fade.to).insert(AnimationOf(context.entity));
+        commands.spawn((
+            AnimationOf(context.entity),
+            AnimationDuration(fade.duration),
+            CrossFadeRamp,
+        ));
+    }
+
+    pub(super) fn update_weight(
+        ramps: Query<
+            (&AnimationOf, &AnimationDuration, &PlayheadMove),
+            (With<CrossFadeRamp>, Changed<PlayheadMove>),
+        >,
+        mut fades: Query<(&Self, &mut CrossFadeWeight)>,
+        mut commands: Commands,
+    ) {
+        for (parent, duration, movement) in &ramps {
+            let Ok((fade, mut weight)) = fades.get_mut(parent.0) else {
+                continue;
+            };
+
+            weight.0 = get_time(duration.0, movement.end, None);
+
+            // The buffered values only refer to the fade itself,
+            // so they still resolve once `from` is gone.
+            let duration = duration.0.as_secs_f32();
+            let finished = movement.start < duration || duration == 0.0;
+            if finished && movement.end >= duration {
+                commands.entity(fade.from).try_despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use super::{
+    AnimationBuilder, AnimationCurve, AnimationDuration, Delta, DynamicFieldLens, Keyframe,
+    lerp::AnimationLerp,
+};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec2, Vec3, Vec4, curve::EaseFunction};
+use bevy_platform::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A serializable description of an animation hierarchy.
+///
+/// Lenses can't be serialized, so leaves refer to them by
+/// the name they were registered with in the [`LensRegistry`].
+///
+/// ```ignore
+/// let data = AnimationData::Sequence(vec![
+///     AnimationData::Keyframe(LeafData::new("Transform.translation", Vec3::X, 0.5)),
+///     AnimationData::Delay(0.25),
+/// ]);
+///
+/// commands.spawn((TimeDriver::default(), data.to_bundle(&registry)?));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnimationData {
+    /// Children that run one after another.
+    Sequence(Vec<AnimationData>),
+    /// Children that all run at once.
+    Parallel(Vec<AnimationData>),
+    /// A node that does nothing for some number of seconds.
+    Delay(f32),
+    Keyframe(LeafData),
+    Delta(LeafData),
+}
+
+/// A serializable [`Keyframe`] or [`Delta`] node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeafData {
+    /// The name of a lens in the [`LensRegistry`].
+    pub lens: String,
+    pub value: AnimationValue,
+    /// The duration in seconds.
+    pub duration: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<EaseFunction>,
+}
+
+impl LeafData {
+    pub fn new(lens: impl Into<String>, value: impl Into<AnimationValue>, duration: f32) -> Self {
+        Self {
+            lens: lens.into(),
+            value: value.into(),
+            duration,
+            curve: None,
+        }
+    }
+
+    pub fn with_curve(mut self, curve: EaseFunction) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+}
+
+/// A serializable value of one of the common animated types.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AnimationValue {
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Quat(Quat),
+    Color(Color),
+}
+
+macro_rules! impl_value {
+    ($($variant:ident($ty:ty)),*) => {
+        $(
+            impl From<$ty> for AnimationValue {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl TryFrom<AnimationValue> for $ty {
+                type Error = AnimationValue;
+
+                fn try_from(value: AnimationValue) -> Result<Self, Self::Error> {
+                    match value {
+                        AnimationValue::$variant(value) => Ok(value),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_value!(
+    F32(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+    Quat(Quat),
+    Color(Color)
+);
+
+#[derive(Debug, Clone, Copy)]
+enum LeafKind {
+    Keyframe,
+    Delta,
+}
+
+type AddLeaf =
+    Arc<dyn Fn(AnimationBuilder, &LeafData, LeafKind) -> Result<AnimationBuilder> + Send + Sync>;
+
+/// Lenses available to [`AnimationData`], by name.
+///
+/// ```ignore
+/// registry.register("Transform.translation", lens!(Transform::translation));
+/// ```
+#[derive(Resource, Default)]
+pub struct LensRegistry(HashMap<String, AddLeaf>);
+
+impl LensRegistry {
+    /// Register a lens under `name`, replacing any lens already registered with it.
+    pub fn register<T>(
+        &mut self,
+        name: impl Into<String>,
+        lens: impl Into<DynamicFieldLens<T>>,
+    ) -> &mut Self
+    where
+        T: AnimationLerp + TryFrom<AnimationValue>,
+    {
+        let lens = lens.into();
+        let add: AddLeaf = Arc::new(move |builder, leaf, kind| {
+            let value = T::try_from(leaf.value).map_err(|_| {
+                format!(
+                    "lens `{}` animates a `{}`, but the value is {:?}",
+                    leaf.lens,
+                    core::any::type_name::<T>(),
+                    leaf.value,
+                )
+            })?;
+
+            let duration = AnimationDuration::try_secs(leaf.duration)
+                .map_err(|e| format!("lens `{}` has an invalid duration: {e}", leaf.lens))?;
+
+            let node = (
+                lens.clone(),
+                duration,
+                AnimationCurve::from(leaf.curve.unwrap_or(EaseFunction::Linear)),
+            );
+
+            Ok(match kind {
+                LeafKind::Keyframe => builder.then((node, Keyframe(value))),
+                LeafKind::Delta => builder.then((node, Delta(value))),
+            })
+        });
+
+        self.0.insert(name.into(), add);
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+}
+
+impl AnimationData {
+    /// Build the hierarchy, resolving lenses with `registry`.
+    ///
+    /// A lone leaf or delay is wrapped in a sequence.
+    pub fn to_bundle(&self, registry: &LensRegistry) -> Result<impl Bundle> {
+        let builder = match self {
+            Self::Sequence(children) => {
+                Self::add_children(AnimationBuilder::sequence(), children, registry)?
+            }
+            Self::Parallel(children) => {
+                Self::add_children(AnimationBuilder::parallel(), children, registry)?
+            }
+            leaf => leaf.add_to(AnimationBuilder::sequence(), registry)?,
+        };
+
+        Ok(builder.build())
+    }
+
+    fn add_children(
+        builder: AnimationBuilder,
+        children: &[AnimationData],
+        registry: &LensRegistry,
+    ) -> Result<AnimationBuilder> {
+        children
+            .iter()
+            .try_fold(builder, |builder, child| child.add_to(builder, registry))
+    }
+
+    fn add_to(
+        &self,
+        builder: AnimationBuilder,
+        registry: &LensRegistry,
+    ) -> Result<AnimationBuilder> {
+        let (leaf, kind) = match self {
+            Self::Sequence(children) => {
+                let nested = Self::add_children(AnimationBuilder::sequence(), children, registry)?;
+                return Ok(builder.nest(nested));
+            }
+            Self::Parallel(children) => {
+                let nested = Self::add_children(AnimationBuilder::parallel(), children, registry)?;
+                return Ok(builder.nest(nested));
+            }
+            Self::Delay(seconds) => {
+                let duration = AnimationDuration::try_secs(*seconds)
+                    .map_err(|e| format!("invalid delay of {seconds} seconds: {e}"))?;
+                return Ok(builder.then(duration));
+            }
+            Self::Keyframe(leaf) => (leaf, LeafKind::Keyframe),
+            Self::Delta(leaf) => (leaf, LeafKind::Delta),
+        };
+
+        let add = registry
+            .0
+            .get(&leaf.lens)
+            .ok_or_else(|| format!("no lens is registered as `{}`", leaf.lens))?;
+
+        add(builder, leaf, kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnimationTarget, lens, playhead::step_animation, testing::app};
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn sequences_round_trip_and_play() {
+        let data = AnimationData::Sequence(vec![
+            AnimationData::Keyframe(
+                LeafData::new("Transform.translation", Vec3::X, 0.5)
+                    .with_curve(EaseFunction::QuadraticInOut),
+            ),
+            AnimationData::Delay(0.25),
+            AnimationData::Parallel(vec![
+                AnimationData::Delta(LeafData::new("Transform.translation", Vec3::Y, 0.5)),
+                AnimationData::Keyframe(LeafData::new("Transform.scale", Vec3::splat(2.0), 1.0)),
+            ]),
+        ]);
+
+        let text = ron::to_string(&data).unwrap();
+        let parsed: AnimationData = ron::from_str(&text).unwrap();
+        assert_eq!(parsed, data);
+
+        let mut app = app(0.1);
+        app.world_mut()
+            .resource_mut::<LensRegistry>()
+            .register("Transform.translation", lens!(Transform::translation))
+            .register("Transform.scale", lens!(Transform::scale));
+
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let bundle = parsed
+            .to_bundle(app.world().resource::<LensRegistry>())
+            .unwrap();
+        let root = app
+            .world_mut()
+            .spawn((AnimationTarget(target), bundle))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.75).unwrap();
+        let transform = app.world().get::<Transform>(target).unwrap();
+        assert!(transform.translation.abs_diff_eq(Vec3::X + Vec3::Y, 1e-5));
+        assert_eq!(transform.scale, Vec3::splat(2.0));
+    }
+}
@@ -0,0 +1,55 @@
+use super::{AnimationSystems, playhead::AnimationPlayhead};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+
+/// Records how much work the animation systems do each frame.
+///
+/// This is opt-in, since counting isn't free. Add it alongside the
+/// [`KeyframePlugin`](crate::KeyframePlugin) and read [`KeyframeDiagnostics`].
+#[derive(Debug, Default)]
+pub struct KeyframeDiagnosticsPlugin;
+
+impl Plugin for KeyframeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyframeDiagnostics>()
+            .add_systems(First, KeyframeDiagnostics::reset)
+            .add_systems(
+                PreUpdate,
+                KeyframeDiagnostics::count_playheads.after(AnimationSystems::Animate),
+            );
+    }
+}
+
+/// Counts of the animation work done this frame.
+///
+/// Counts are reset in `First`, so they're complete from `Update` onward.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeyframeDiagnostics {
+    /// The number of playheads that moved since the last frame.
+    pub active_playheads: usize,
+    /// The number of leaf movements applied.
+    pub movements: usize,
+    /// The number of times the [`Animate`](crate::Animate) schedule ran.
+    pub schedule_runs: usize,
+}
+
+impl KeyframeDiagnostics {
+    fn reset(mut diagnostics: ResMut<Self>) {
+        *diagnostics = Self::default();
+    }
+
+    fn count_playheads(
+        playheads: Query<(), Changed<AnimationPlayhead>>,
+        mut diagnostics: ResMut<Self>,
+    ) {
+        diagnostics.active_playheads = playheads.iter().count();
+    }
+
+    /// Record a run of the [`Animate`](crate::Animate) schedule that applied `movements` movements.
+    pub(crate) fn record_run(world: &mut World, movements: usize) {
+        if let Some(mut diagnostics) = world.get_resource_mut::<Self>() {
+            diagnostics.movements += movements;
+            diagnostics.schedule_runs += 1;
+        }
+    }
+}
@@ -1,20 +1,67 @@
-use super::playhead::{AnimationPlayhead, SequenceEvent};
-use bevy_ecs::prelude::*;
+use super::{
+    AnimationComplete, AnimationSystems,
+    dynamic_systems::DynamicSystems,
+    playhead::{AnimationPlayhead, PlayheadDriver, SequenceEvent, SequenceLayout, settle_playhead},
+};
+use bevy_app::PreUpdate;
+use bevy_ecs::{
+    component::HookContext,
+    prelude::*,
+    system::{BoxedSystem, SystemId},
+    world::DeferredWorld,
+};
+use bevy_math::Curve;
+use bevy_reflect::{Reflect, std_traits::ReflectDefault};
 use bevy_time::prelude::*;
+use core::marker::PhantomData;
+use std::sync::Arc;
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Reflect, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[reflect(Debug, PartialEq, Clone, Hash)]
 pub enum PlaybackState {
     Play,
     Pause,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// The playback state of every driver in the app.
+///
+/// Pausing this freezes all playheads driven by the [`AnimationSystems::Driver`]
+/// set, and playing resumes them from where they stopped. Drivers paused
+/// individually stay paused.
+#[derive(Resource, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GlobalPlaybackState(pub PlaybackState);
+
+impl Default for GlobalPlaybackState {
+    fn default() -> Self {
+        Self(PlaybackState::Play)
+    }
+}
+
+impl GlobalPlaybackState {
+    pub(super) fn is_playing(state: Res<Self>) -> bool {
+        state.0 == PlaybackState::Play
+    }
+}
+
+#[derive(Reflect, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[reflect(Debug, PartialEq, Clone, Hash)]
 pub enum PlaybackMode {
     Once,
     Repeat(RepeatMode),
+    /// Play a fixed number of loops, then stop as if [`PlaybackMode::Once`].
+    ///
+    /// A loop is a single pass through the sequence in either direction, so
+    /// with [`RepeatMode::PingPong`], each round trip counts as two loops.
+    RepeatN(RepeatMode, u32),
+    /// Play forward, then backward, then stop at the start.
+    ///
+    /// This plays like `RepeatN(RepeatMode::PingPong, 2)`, but the driver
+    /// faces forward again when it stops, so playing it again goes out and back.
+    PingPongOnce,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Reflect, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[reflect(Debug, PartialEq, Clone, Hash)]
 pub enum RepeatMode {
     /// Restart the animation.
     Restart,
@@ -22,12 +69,29 @@ pub enum RepeatMode {
     PingPong,
 }
 
-#[derive(Component, Debug, PartialEq)]
-#[require(AnimationPlayhead)]
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+#[require(PlayheadDriver)]
 pub struct TimeDriver {
+    /// The rate at which the playhead advances.
+    ///
+    /// A speed of zero freezes the playhead. Negative speeds play the
+    /// sequence backward, completing when the playhead reaches zero.
+    /// If the playhead is already at zero, reversed playback begins at the
+    /// end of the sequence.
+    ///
+    /// Reversed playback triggers [`SequenceEvent::SequenceStarted`] as it
+    /// leaves the end and [`SequenceEvent::SequenceCompleted`] on reaching
+    /// zero, where a [`PlaybackMode::Once`] driver pauses.
+    ///
+    /// The speed can be animated like any other field, with
+    /// `lens!(TimeDriver::speed)` targeting the driven entity, to ramp
+    /// playback in and out of slow motion. Changes take effect on the
+    /// next frame, since drivers move their playheads before animating.
     pub speed: f32,
     pub state: PlaybackState,
     pub mode: PlaybackMode,
+    loops: u32,
 }
 
 impl Default for TimeDriver {
@@ -36,12 +100,151 @@ impl Default for TimeDriver {
             speed: 1.0,
             state: PlaybackState::Play,
             mode: PlaybackMode::Once,
+            loops: 0,
         }
     }
 }
 
+/// Commands for controlling an entity's [`TimeDriver`].
+pub trait TimeDriverCommands {
+    /// Jump the playhead back to the start and resume playback.
+    ///
+    /// The jump doesn't trigger side effects, so animation targets keep
+    /// their current values and `Keyframe`s re-capture their starting value
+    /// when the playhead next enters them. Movements already applied this
+    /// frame are unaffected, since the restart is applied when commands are.
+    fn restart_animation(&mut self) -> &mut Self;
+
+    /// Restart the animation fresh from the targets' current values.
+    ///
+    /// Like [`restart_animation`](Self::restart_animation), but the driver
+    /// also faces forward, so the next sweep enters every node from its start
+    /// and each `Keyframe` and `Shift` re-captures where it starts from. This
+    /// suits interrupting an animation and playing it again, like when a
+    /// button is hovered mid-animation.
+    fn replay_animation(&mut self) -> &mut Self;
+
+    /// Stop the animation where it is, or [`Settle`] it at one of its ends.
+    ///
+    /// This pauses the driver, evaluates everything the playhead sweeps
+    /// over on the way to where it settles, then applies the entity's
+    /// [`AnimationComplete`](crate::AnimationComplete) policy. No
+    /// [`SequenceEvent`]s are triggered, since the sequence didn't complete,
+    /// so repeating drivers don't loop and a
+    /// [`WhenComplete`](crate::WhenComplete) isn't started.
+    ///
+    /// Other drivers, like a [`SampleRunner`], keep moving the playhead
+    /// unless the completion policy removes them.
+    fn cancel_animation(&mut self, settle: Settle) -> &mut Self;
+}
+
+/// Where a cancelled animation leaves its playhead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Settle {
+    /// Stop where the playhead is.
+    #[default]
+    Leave,
+    /// Finish the animation, as if it had played to the end.
+    ToEnd,
+    /// Return to the start of the animation.
+    ToStart,
+}
+
+impl TimeDriverCommands for EntityCommands<'_> {
+    fn restart_animation(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            if let Some(mut playhead) = entity.get_mut::<AnimationPlayhead>() {
+                playhead.jump_to(0.0);
+            }
+
+            if let Some(mut driver) = entity.get_mut::<TimeDriver>() {
+                driver.loops = 0;
+                driver.play();
+            }
+        })
+    }
+
+    fn replay_animation(&mut self) -> &mut Self {
+        self.queue(|mut entity: EntityWorldMut| {
+            if let Some(mut driver) = entity.get_mut::<TimeDriver>() {
+                driver.speed = driver.speed.abs();
+            }
+        });
+        self.restart_animation()
+    }
+
+    fn cancel_animation(&mut self, settle: Settle) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| -> Result {
+            if let Some(mut driver) = entity.get_mut::<TimeDriver>() {
+                driver.pause();
+            }
+
+            let position = match settle {
+                Settle::Leave => None,
+                Settle::ToStart => Some(0.0),
+                Settle::ToEnd => Some(
+                    entity
+                        .get::<SequenceLayout>()
+                        .map(SequenceLayout::total)
+                        .unwrap_or_default(),
+                ),
+            };
+
+            let id = entity.id();
+            entity.world_scope(|world| settle_playhead(world, id, position))?;
+
+            // Callbacks run while settling may have despawned the entity already.
+            if !entity.is_despawned() {
+                AnimationComplete::apply(entity);
+            }
+
+            Ok(())
+        })
+    }
+}
+
 impl TimeDriver {
+    /// A driver that waits for [`TimeDriver::play`] to start.
+    ///
+    /// To start from somewhere other than the beginning, spawn it alongside an
+    /// [`AnimationPlayhead::at`], which takes the place of the default playhead.
+    pub fn paused() -> Self {
+        Self {
+            state: PlaybackState::Pause,
+            ..Default::default()
+        }
+    }
+
+    /// A driver that plays with the given [`PlaybackMode`].
+    pub fn with_mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The number of loops the driver has played.
+    ///
+    /// A loop is a single pass through the sequence in either direction,
+    /// so a [`RepeatMode::PingPong`] round trip counts as two. This resets
+    /// when the animation is restarted or replayed after running out.
+    pub fn loops(&self) -> u32 {
+        self.loops
+    }
+
+    /// Resume playback.
+    ///
+    /// If a [`PlaybackMode::RepeatN`] or [`PlaybackMode::PingPongOnce`]
+    /// has run out of loops, this resets the loop count.
     pub fn play(&mut self) {
+        let count = match self.mode {
+            PlaybackMode::RepeatN(_, count) => Some(count),
+            PlaybackMode::PingPongOnce => Some(2),
+            _ => None,
+        };
+
+        if count.is_some_and(|count| self.loops >= count) {
+            self.loops = 0;
+        }
+
         self.state = PlaybackState::Play;
     }
 
@@ -49,38 +252,677 @@ impl TimeDriver {
         self.state = PlaybackState::Pause;
     }
 
-    pub(super) fn drive_playhead(mut q: Query<(&Self, &mut AnimationPlayhead)>, time: Res<Time>) {
+    /// Reverse the playback direction from the current position.
+    ///
+    /// Reversed playback fires the same sequence events as forward playback,
+    /// starting from the end and completing at the start.
+    pub fn reverse(&mut self) {
+        self.speed = -self.speed;
+    }
+
+    pub(super) fn drive_playhead(
+        mut q: Query<
+            (
+                &Self,
+                &mut AnimationPlayhead,
+                &SequenceLayout,
+                Option<&SpeedRamp>,
+            ),
+            Without<CustomClock>,
+        >,
+        time: Res<Time>,
+    ) {
+        let delta = time.delta_secs();
+        for (driver, playhead, layout, ramp) in &mut q {
+            driver.advance(playhead, layout.total(), ramp, delta);
+        }
+    }
+
+    fn drive_playhead_with<C: Default + Send + Sync + 'static>(
+        mut q: Query<
+            (
+                &Self,
+                &mut AnimationPlayhead,
+                &SequenceLayout,
+                Option<&SpeedRamp>,
+            ),
+            With<DriverClock<C>>,
+        >,
+        time: Res<Time<C>>,
+    ) {
+        let delta = time.delta_secs();
+        for (driver, playhead, layout, ramp) in &mut q {
+            driver.advance(playhead, layout.total(), ramp, delta);
+        }
+    }
+
+    pub(super) fn drive_playhead_fixed(
+        mut q: Query<
+            (
+                &Self,
+                &mut AnimationPlayhead,
+                &SequenceLayout,
+                Option<&SpeedRamp>,
+            ),
+            With<FixedTimeDriver>,
+        >,
+        time: Res<Time<Fixed>>,
+    ) {
         let delta = time.delta_secs();
-        for (driver, mut playhead) in &mut q {
-            let speed = driver.speed;
+        for (driver, playhead, layout, ramp) in &mut q {
+            driver.advance(playhead, layout.total(), ramp, delta);
+        }
+    }
 
-            *playhead.get_mut() += delta * speed;
+    fn advance(
+        &self,
+        mut playhead: Mut<AnimationPlayhead>,
+        total: f32,
+        ramp: Option<&SpeedRamp>,
+        delta: f32,
+    ) {
+        if self.state == PlaybackState::Pause || self.speed == 0.0 {
+            return;
+        }
+
+        let mut speed = self.speed;
+
+        // There's nothing behind the start, so reversed
+        // playback from the start begins at the end.
+        if speed < 0.0 && playhead.get() <= 0.0 {
+            playhead.jump_to(total);
+        }
+
+        if let Some(ramp) = ramp {
+            speed *= ramp.0.sample_clamped(playhead.progress(total));
         }
+
+        *playhead.get_mut() += delta * speed;
     }
 
     pub(super) fn observe_sequence(
         trigger: Trigger<SequenceEvent>,
-        mut driver: Query<(&mut TimeDriver, &mut AnimationPlayhead)>,
+        mut driver: Query<(&mut TimeDriver, &mut AnimationPlayhead, &SequenceLayout)>,
+        mut commands: Commands,
     ) {
         if !matches!(*trigger, SequenceEvent::SequenceCompleted) {
             return;
         }
-        let Ok((mut driver, mut playhead)) = driver.get_mut(trigger.target()) else {
+        let Ok((mut driver, mut playhead, layout)) = driver.get_mut(trigger.target()) else {
             return;
         };
 
-        match driver.mode {
-            PlaybackMode::Once => {
+        driver.loops = driver.loops.saturating_add(1);
+
+        let repeat = match driver.mode {
+            PlaybackMode::Once => None,
+            PlaybackMode::Repeat(mode) => Some(mode),
+            PlaybackMode::RepeatN(mode, count) => (driver.loops < count).then_some(mode),
+            // The first completion is the end of the forward leg.
+            PlaybackMode::PingPongOnce => (driver.loops < 2).then_some(RepeatMode::PingPong),
+        };
+
+        if repeat.is_some() {
+            commands.trigger_targets(
+                SequenceEvent::SequenceLooped {
+                    loops: driver.loops,
+                },
+                trigger.target(),
+            );
+        }
+
+        match repeat {
+            None => {
                 driver.pause();
+
+                // Face forward again so the next play goes out and back.
+                if driver.mode == PlaybackMode::PingPongOnce {
+                    driver.reverse();
+                }
             }
-            PlaybackMode::Repeat(RepeatMode::Restart) => {
-                // TODO: this doesn't wrap properly since it'll chop off
-                // whatever fractional end bit there was
-                playhead.jump_to(0.0);
+            Some(RepeatMode::Restart) => {
+                let duration = layout.total();
+                let reversed = driver.speed < 0.0;
+
+                // Wrap any time past the end back to the start so fast or
+                // choppy playback doesn't drift.
+                let past_end = if reversed {
+                    -playhead.get()
+                } else {
+                    playhead.get() - duration
+                };
+                let overshoot = if duration > 0.0 {
+                    past_end.max(0.0) % duration
+                } else {
+                    0.0
+                };
+
+                // Leaving the previous position at the start means the wrapped
+                // portion is swept on the next movement pass.
+                if reversed {
+                    playhead.jump_to(duration);
+                    playhead.set(duration - overshoot);
+                } else {
+                    playhead.jump_to(0.0);
+                    playhead.set(overshoot);
+                }
             }
-            PlaybackMode::Repeat(RepeatMode::PingPong) => {
-                driver.speed = -driver.speed;
+            Some(RepeatMode::PingPong) => {
+                driver.reverse();
             }
         }
     }
 }
+
+/// Varies a [`TimeDriver`]'s speed over the course of its sequence.
+///
+/// The curve maps the playhead's progress, from `0.0` to `1.0`, to a multiplier
+/// on the driver's speed, so a sequence can slow down into a hit and then
+/// recover quickly. This remaps time for the whole sequence, while an
+/// [`AnimationCurve`](crate::AnimationCurve) remaps the value within a single
+/// node, so the two compose: each node's curve is sampled at the time
+/// the ramped playhead reaches.
+///
+/// A multiplier of zero stalls the playhead where it is.
+#[derive(Component, Clone)]
+#[require(TimeDriver)]
+pub struct SpeedRamp(pub Arc<dyn Curve<f32> + Send + Sync>);
+
+impl SpeedRamp {
+    pub fn new(curve: impl Curve<f32> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(curve))
+    }
+}
+
+impl core::fmt::Debug for SpeedRamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SpeedRamp").field(&self.0.domain()).finish()
+    }
+}
+
+/// Drives a [`TimeDriver`] with the clock `Time<C>` rather than the default `Time`.
+///
+/// The default `Time` follows virtual time, so it stops when the game is paused.
+/// Use `DriverClock<Real>` for animations that should keep running, like UI.
+/// Custom clocks must be inserted as a `Time<C>` resource.
+#[derive(Component)]
+#[require(TimeDriver, CustomClock)]
+#[component(on_add = Self::on_add_hook)]
+pub struct DriverClock<C: Default + Send + Sync + 'static>(PhantomData<fn() -> C>);
+
+impl<C: Default + Send + Sync + 'static> Default for DriverClock<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: Default + Send + Sync + 'static> core::fmt::Debug for DriverClock<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DriverClock")
+            .field(&core::any::type_name::<C>())
+            .finish()
+    }
+}
+
+impl<C: Default + Send + Sync + 'static> DriverClock<C> {
+    fn on_add_hook(mut world: DeferredWorld, context: HookContext) {
+        if let Some(mut clock) = world.get_mut::<CustomClock>(context.entity) {
+            clock.0 = Some(|entity| {
+                entity.remove::<Self>();
+            });
+        }
+
+        world.commands().add_systems_dynamic(PreUpdate, || {
+            TimeDriver::drive_playhead_with::<C>.in_set(AnimationSystems::Driver)
+        });
+    }
+}
+
+/// Drives a [`TimeDriver`] in the fixed timestep, in lockstep with physics.
+///
+/// The playhead advances by `Time<Fixed>` in `FixedPreUpdate`, and the
+/// animations it sweeps over are evaluated immediately, so systems in
+/// `FixedUpdate` see the animated values. Fields with a
+/// [`BlendMode`](crate::BlendMode) are still resolved in `PreUpdate`.
+#[derive(Component, Debug, Default, PartialEq, Eq)]
+#[require(TimeDriver, CustomClock)]
+pub struct FixedTimeDriver;
+
+/// Excludes drivers with a [`DriverClock`] or [`FixedTimeDriver`] from the default clock.
+///
+/// A [`DriverClock`] also stores how to remove itself, since its clock
+/// type is erased once the animation completes.
+#[derive(Component, Default)]
+pub(crate) struct CustomClock(pub(crate) Option<fn(&mut EntityWorldMut)>);
+
+/// Drives the playhead from an external clock, like the playhead of a sample.
+///
+/// The audio backend (or any other clock) is expected to write the
+/// current position into [`SamplePosition`]. Since the animation follows
+/// this position directly, it respects any pausing, changes in speed,
+/// or reversed playback of the source.
+#[derive(Component, Debug, Default, PartialEq, Eq)]
+#[require(PlayheadDriver, SamplePosition)]
+pub struct SampleRunner;
+
+/// The position of an external clock in seconds.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct SamplePosition(pub f32);
+
+impl SampleRunner {
+    pub(super) fn drive_playhead(
+        mut q: Query<
+            (&SamplePosition, &mut AnimationPlayhead),
+            (With<Self>, Changed<SamplePosition>),
+        >,
+    ) {
+        for (position, mut playhead) in &mut q {
+            if playhead.get() != position.0 {
+                playhead.seek_to(position.0);
+            }
+        }
+    }
+}
+
+/// Drives the playhead from a quantity computed from the world, rather than time.
+///
+/// Each frame, the system returns the playhead's target position in seconds,
+/// which is clamped to the sequence's duration. This suits parameters like
+/// how far the player is inside a building, which have no inherent forward
+/// motion, so these playheads never complete or loop: [`AnimationComplete`]
+/// leaves them alone, though [`SequenceEvent`]s still fire as the parameter
+/// crosses either end.
+///
+/// ```ignore
+/// #[derive(Resource)]
+/// struct InsideAmount(f32);
+///
+/// commands.spawn((
+///     ParamDriver::new(|inside: Res<InsideAmount>| inside.0),
+///     animations![(Keyframe(0.25), AnimationDuration::secs(1.0))],
+/// ));
+/// ```
+///
+/// To drive the playhead by its fraction of the sequence instead, use
+/// [`ParamDriver::normalized`].
+///
+/// [`AnimationComplete`]: crate::AnimationComplete
+#[derive(Component)]
+#[require(PlayheadDriver)]
+#[component(on_add = Self::on_add_hook, on_remove = Self::on_remove_hook)]
+pub struct ParamDriver {
+    system: ParamSystem,
+    normalized: bool,
+}
+
+enum ParamSystem {
+    Pending(BoxedSystem<(), f32>),
+    Registered(SystemId<(), f32>),
+    Unregistered,
+}
+
+impl core::fmt::Debug for ParamDriver {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let system = match &self.system {
+            ParamSystem::Pending(system) => system.name(),
+            ParamSystem::Registered(_) => "registered".into(),
+            ParamSystem::Unregistered => "unregistered".into(),
+        };
+
+        f.debug_struct("ParamDriver")
+            .field("system", &system)
+            .field("normalized", &self.normalized)
+            .finish()
+    }
+}
+
+impl ParamDriver {
+    pub fn new<M>(system: impl IntoSystem<(), f32, M>) -> Self {
+        Self {
+            system: ParamSystem::Pending(Box::new(IntoSystem::into_system(system))),
+            normalized: false,
+        }
+    }
+
+    /// Drive the playhead with a fraction of the sequence, from `0.0` to `1.0`.
+    ///
+    /// This is convenient for parameters that are already normalized,
+    /// since the sequence's duration can change without updating them.
+    pub fn normalized<M>(system: impl IntoSystem<(), f32, M>) -> Self {
+        Self {
+            normalized: true,
+            ..Self::new(system)
+        }
+    }
+
+    fn on_add_hook(mut world: DeferredWorld, context: HookContext) {
+        let entity = context.entity;
+        world.commands().queue(move |world: &mut World| {
+            let Some(mut driver) = world.get_mut::<Self>(entity) else {
+                return;
+            };
+
+            let ParamSystem::Pending(system) =
+                core::mem::replace(&mut driver.system, ParamSystem::Unregistered)
+            else {
+                return;
+            };
+
+            let id = world.register_boxed_system(system);
+            if let Some(mut driver) = world.get_mut::<Self>(entity) {
+                driver.system = ParamSystem::Registered(id);
+            }
+        });
+    }
+
+    fn on_remove_hook(mut world: DeferredWorld, context: HookContext) {
+        let Some(ParamSystem::Registered(id)) =
+            world.get::<Self>(context.entity).map(|d| &d.system)
+        else {
+            return;
+        };
+
+        let id = *id;
+        world.commands().queue(move |world: &mut World| {
+            let _ = world.unregister_system(id);
+        });
+    }
+
+    pub(super) fn drive_playhead(world: &mut World) -> Result {
+        let drivers: Vec<_> = world
+            .query::<(Entity, &Self)>()
+            .iter(world)
+            .filter_map(|(entity, driver)| match driver.system {
+                ParamSystem::Registered(id) => Some((entity, id, driver.normalized)),
+                _ => None,
+            })
+            .collect();
+
+        // One failing parameter shouldn't stop the other drivers.
+        let mut first_error = None;
+        for (entity, id, normalized) in drivers {
+            let position = match world.run_system(id) {
+                Ok(position) => position,
+                Err(e) => {
+                    first_error.get_or_insert(e.into());
+                    continue;
+                }
+            };
+
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            let total = entity
+                .get::<SequenceLayout>()
+                .map(SequenceLayout::total)
+                .unwrap_or_default();
+            let Some(mut playhead) = entity.get_mut::<AnimationPlayhead>() else {
+                continue;
+            };
+
+            let position = if normalized {
+                position * total
+            } else {
+                position
+            };
+            let position = position.clamp(0.0, total);
+            if playhead.get() != position {
+                playhead.seek_to(position);
+            }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AnimationDuration, AnimationTarget, Delta, Keyframe, animations, lens, testing::app,
+    };
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn ping_pong_loops_count_each_pass() {
+        let mut app = app(0.25);
+        let driver = app
+            .world_mut()
+            .spawn((
+                TimeDriver::default().with_mode(PlaybackMode::RepeatN(RepeatMode::PingPong, 3)),
+                animations![AnimationDuration::secs(1.0)],
+            ))
+            .id();
+
+        for _ in 0..30 {
+            app.update();
+        }
+
+        let world = app.world();
+        let time_driver = world.get::<TimeDriver>(driver).unwrap();
+        assert_eq!(time_driver.loops(), 3);
+        assert_eq!(time_driver.state, PlaybackState::Pause);
+        assert_eq!(world.get::<AnimationPlayhead>(driver).unwrap().get(), 1.0);
+    }
+
+    #[test]
+    fn reversing_midway_returns_to_the_start() {
+        #[derive(Resource, Default)]
+        struct Events(Vec<String>);
+
+        let mut app = app(0.1);
+        app.init_resource::<Events>();
+
+        let start = Vec3::new(1.0, 2.0, 3.0);
+        let target = app
+            .world_mut()
+            .spawn(Transform::from_translation(start))
+            .id();
+        let driver = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver::default(),
+                animations![(
+                    Keyframe(Vec3::new(11.0, 2.0, 3.0)),
+                    AnimationDuration::secs(1.0)
+                )],
+            ))
+            .observe(
+                |trigger: Trigger<SequenceEvent>, mut events: ResMut<Events>| {
+                    events.0.push(format!("{:?}", trigger.event()));
+                },
+            )
+            .id();
+
+        let position =
+            |app: &bevy_app::App| app.world().get::<AnimationPlayhead>(driver).unwrap().get();
+        while position(&app) < 0.5 {
+            app.update();
+        }
+
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert!(translation.x > start.x);
+
+        app.world_mut()
+            .get_mut::<TimeDriver>(driver)
+            .unwrap()
+            .reverse();
+        for _ in 0..20 {
+            app.update();
+        }
+
+        // The playhead may overshoot the start, but the value can't.
+        assert!(position(&app) <= 0.0);
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert_eq!(translation, start);
+        assert_eq!(
+            app.world().resource::<Events>().0,
+            ["SequenceStarted", "SequenceCompleted"],
+        );
+    }
+
+    #[test]
+    fn reversed_once_from_the_start_plays_back_from_the_end() {
+        #[derive(Resource, Default)]
+        struct Events(Vec<String>);
+
+        let mut app = app(0.1);
+        app.init_resource::<Events>();
+
+        let start = Vec3::new(1.0, 2.0, 3.0);
+        let end = Vec3::new(11.0, 2.0, 3.0);
+        let target = app
+            .world_mut()
+            .spawn(Transform::from_translation(start))
+            .id();
+        let driver = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver {
+                    speed: -1.0,
+                    ..Default::default()
+                },
+                animations![(Keyframe(end), AnimationDuration::secs(1.0))],
+            ))
+            .observe(
+                |trigger: Trigger<SequenceEvent>, mut events: ResMut<Events>| {
+                    events.0.push(format!("{:?}", trigger.event()));
+                },
+            )
+            .id();
+
+        // The first frame jumps to the end and starts back from there.
+        app.update();
+        app.update();
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert!(translation.x > start.x + 8.0, "{translation}");
+
+        for _ in 0..20 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(world.get::<Transform>(target).unwrap().translation, start);
+        assert_eq!(
+            world.get::<TimeDriver>(driver).unwrap().state,
+            PlaybackState::Pause
+        );
+        assert_eq!(
+            world.resource::<Events>().0,
+            ["SequenceStarted", "SequenceCompleted"],
+        );
+    }
+
+    #[test]
+    fn ping_pong_once_stops_at_the_start_facing_forward() {
+        let mut app = app(0.1);
+
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let driver = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver::default().with_mode(PlaybackMode::PingPongOnce),
+                animations![(Keyframe(Vec3::X), AnimationDuration::secs(1.0))],
+            ))
+            .id();
+
+        for _ in 0..40 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert!(world.get::<AnimationPlayhead>(driver).unwrap().get() <= 0.0);
+        let driver = world.get::<TimeDriver>(driver).unwrap();
+        assert_eq!(driver.state, PlaybackState::Pause);
+        assert!(driver.speed > 0.0);
+        assert_eq!(
+            world.get::<Transform>(target).unwrap().translation,
+            Vec3::ZERO
+        );
+    }
+
+    #[test]
+    fn failing_params_dont_stop_other_drivers() {
+        #[derive(Resource)]
+        struct Absent;
+
+        let mut app = app(0.1);
+        let world = app.world_mut();
+        world.spawn((
+            ParamDriver::new(|_: Res<Absent>| 0.5),
+            animations![AnimationDuration::secs(1.0)],
+        ));
+        let driven = world
+            .spawn((
+                ParamDriver::new(|| 0.5),
+                animations![AnimationDuration::secs(1.0)],
+            ))
+            .id();
+
+        world.run_system_cached(SequenceLayout::update).unwrap();
+        let result = world
+            .run_system_cached(ParamDriver::drive_playhead)
+            .unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(world.get::<AnimationPlayhead>(driven).unwrap().get(), 0.5);
+    }
+
+    #[test]
+    fn nested_drivers_combine_on_one_target() {
+        let mut app = app(0.1);
+
+        let fast = TimeDriver {
+            speed: 2.0,
+            ..Default::default()
+        };
+
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let slow = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver::default(),
+                animations![
+                    (Delta(Vec3::X), AnimationDuration::secs(1.0)),
+                    (
+                        fast,
+                        animations![(Delta(Vec3::Y), AnimationDuration::secs(1.0))]
+                    ),
+                ],
+            ))
+            .id();
+
+        // The fast subtree plays on its own, so the slow
+        // playhead only covers its own delta.
+        app.update();
+        let layout = app.world().get::<SequenceLayout>(slow).unwrap();
+        assert_eq!(layout.total(), 1.0);
+
+        let position =
+            |app: &bevy_app::App| app.world().get::<AnimationPlayhead>(slow).unwrap().get();
+        while position(&app) < 0.6 {
+            app.update();
+        }
+
+        // Halfway through the slow delta, the fast one is done.
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert!((translation.x - position(&app)).abs() < 1e-4);
+        assert!((translation.y - 1.0).abs() < 1e-4);
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::X + Vec3::Y, 1e-4));
+    }
+}
@@ -45,7 +45,6 @@ pub(super) fn handle_insertions(
     }
 }
 
-#[expect(unused)]
 pub trait DynamicObservers {
     fn add_observer_dynamic<O, E, B, M>(&mut self, systems: O)
     where
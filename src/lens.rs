@@ -1,7 +1,14 @@
-use super::{Animations, lerp::AnimationLerp};
+use super::{AnimationOf, Animations, lerp::AnimationLerp};
 use crate::{
-    AnimationCurve, AnimationDuration, AnimationSystems, AnimationTarget, Delta, Interval,
-    Keyframe, dynamic_systems::DynamicSystems, playhead::PlayheadMove,
+    Animate, AnimationCurve, AnimationDuration, AnimationSystems, AnimationTarget,
+    CapturedBaseline, CapturedInterval, Delta, Interval, Keyframe, Keyframes, Shift,
+    blend::{BlendBuffer, FieldId, resolve_blends},
+    crossfade::{CrossFade, CrossFadeWeight},
+    dynamic_systems::DynamicSystems,
+    modifier::{Modifier, ModifierStart, ModifierState},
+    playhead::PlayheadMove,
+    report_cycle,
+    spline::SplineNeighbors,
 };
 use bevy_app::PreUpdate;
 use bevy_ecs::{
@@ -9,35 +16,123 @@ use bevy_ecs::{
     prelude::*,
     world::{DeferredWorld, EntityMutExcept},
 };
+use bevy_ecs::{reflect::ReflectComponent, world::EntityRefExcept};
+use bevy_log::warn;
+use bevy_platform::collections::HashSet;
+use bevy_reflect::{GetPath, ParsedPath, TypeRegistry};
 use std::{marker::PhantomData, sync::Arc};
 
-// This is kinda stupid, so we'll want to find a better solution.
-pub type FieldGetter<'w, T> = EntityMutExcept<
-    'w,
-    (
-        DynamicFieldLens<T>,
-        Delta<T>,
-        Keyframe<T>,
+/// Declare the components that live on animation nodes rather than their targets.
+///
+/// Animation systems read these while mutably accessing targets through
+/// [`FieldGetter`], and a node can be its own target, so each one must be
+/// excluded there. Bevy only excludes components that are registered when
+/// a system is initialized, so the same list also generates
+/// [`register_node_components`], keeping the two from drifting apart.
+macro_rules! node_components {
+    (shared: [$($shared:ty),* $(,)?], typed<$t:ident>: [$($typed:ty),* $(,)?] $(,)?) => {
+        /// Mutable access to an animation target, excluding every node component.
+        pub(crate) type FieldGetter<'w, $t> =
+            EntityMutExcept<'w, (($($shared,)*), ($($typed,)*))>;
+
+        /// Register every component excluded by [`FieldGetter<T>`].
+        ///
+        /// This must run before any system using the getter is initialized.
+        pub(crate) fn register_node_components<$t: AnimationLerp>(world: &mut World) {
+            $(world.register_component::<$shared>();)*
+            $(world.register_component::<$typed>();)*
+        }
+    };
+}
+
+node_components! {
+    shared: [
         AnimationDuration,
-        AnimationLens<T>,
         AnimationTarget,
+        AnimationCurve,
+        AnimationOf,
         PlayheadMove,
+        CrossFade,
+        CrossFadeWeight,
+        SplineNeighbors,
+        Modifier,
+        Lensed,
+    ],
+    typed<T>: [
+        DynamicFieldLens<T>,
+        AnimationLens<T>,
+        Keyframe<T>,
+        Keyframes<T>,
+        Delta<T>,
+        Shift<T>,
         Interval<T>,
-        AnimationCurve,
-    ),
->;
+        CapturedInterval<T>,
+        CapturedBaseline<T>,
+        ModifierState<T>,
+        ModifierStart<T>,
+    ],
+}
+
+/// Access to an animation target's components from a [`FieldLens`].
+///
+/// The crate's own animation components aren't accessible, since they
+/// may be borrowed while the lens runs.
+pub struct FieldAccess<'w, T: AnimationLerp>(FieldGetter<'w, T>);
+
+impl<'w, T: AnimationLerp> FieldAccess<'w, T> {
+    pub(crate) fn new(entity: FieldGetter<'w, T>) -> Self {
+        Self(entity)
+    }
+
+    /// The animation target.
+    pub fn id(&self) -> Entity {
+        self.0.id()
+    }
+
+    pub fn get<C: Component>(&self) -> Option<&C> {
+        self.0.get::<C>()
+    }
+
+    pub fn get_mut<C: Component<Mutability = Mutable>>(&mut self) -> Option<Mut<'_, C>> {
+        self.0.get_mut::<C>()
+    }
+
+    /// Borrow this access for a shorter lifetime, so it can be used more than once.
+    pub fn reborrow(&mut self) -> FieldAccess<'_, T> {
+        FieldAccess(self.0.reborrow())
+    }
+}
 
 pub trait FieldLens<T: AnimationLerp>: Send + Sync + 'static {
-    fn get_field(&self, entity: FieldGetter<T>) -> Result<T>;
-    fn set_field(&self, entity: FieldGetter<T>, value: T) -> Result;
+    fn get_field(&self, entity: FieldAccess<T>) -> Result<T>;
+    fn set_field(&self, entity: FieldAccess<T>, value: T) -> Result;
+
+    /// Modify the field in place.
+    ///
+    /// By default, this modifies a copy of the field and writes it back.
+    /// Lenses that can borrow the field mutably override this, so large
+    /// values can be animated without being cloned.
+    fn modify_field(&self, mut entity: FieldAccess<T>, modify: &mut dyn FnMut(&mut T)) -> Result {
+        let mut value = self.get_field(entity.reborrow())?;
+        modify(&mut value);
+        self.set_field(entity, value)
+    }
 }
 
 #[derive(Component)]
+#[require(Lensed)]
 pub struct AnimationLens<T: AnimationLerp> {
     lens: Entity,
     _marker: PhantomData<fn() -> T>,
 }
 
+/// Marks a node with an [`AnimationLens`] of any type.
+///
+/// [`Modifier`] nodes are evaluated once for every lens type, so this
+/// tells a node without a lens apart from one with a lens of another type.
+#[derive(Component, Default)]
+pub(crate) struct Lensed;
+
 impl<T: AnimationLerp> AnimationLens<T> {
     pub fn new(lens: Entity) -> Self {
         Self {
@@ -49,12 +144,38 @@ impl<T: AnimationLerp> AnimationLens<T> {
     pub fn get(&self) -> Entity {
         self.lens
     }
+
+    /// Find the lens for an animation node, describing what's missing if it can't be found.
+    pub(crate) fn resolve<'a>(
+        lens_ref: Option<&Self>,
+        lenses: &'a Query<&DynamicFieldLens<T>>,
+        node: Entity,
+    ) -> Result<&'a DynamicFieldLens<T>> {
+        let name = core::any::type_name::<T>();
+        let lens_ref = lens_ref.ok_or_else(|| {
+            format!(
+                "animation node {node} animates a `{name}`, but neither it nor its ancestors \
+                have a `DynamicFieldLens<{name}>`; try adding a `lens!(Component::field)`"
+            )
+        })?;
+
+        let lens = lenses.get(lens_ref.get()).map_err(|_| {
+            format!(
+                "animation node {node} expected a `DynamicFieldLens<{name}>` on {}, \
+                but it was removed",
+                lens_ref.get()
+            )
+        })?;
+
+        Ok(lens)
+    }
 }
 
-fn propagate_lens_ref<T: AnimationLerp>(
+pub(crate) fn propagate_lens_ref<T: AnimationLerp>(
     lenses: Query<Entity, Added<DynamicFieldLens<T>>>,
     hierarchy: Query<&Animations>,
-    conflicts: Query<Has<DynamicFieldLens<T>>>,
+    conflicts: Query<(Has<DynamicFieldLens<T>>, Option<&AnimationLens<T>>)>,
+    parents: Query<&AnimationOf>,
     mut commands: Commands,
 ) -> Result {
     for new_lens_entity in &lenses {
@@ -62,48 +183,81 @@ fn propagate_lens_ref<T: AnimationLerp>(
             .entity(new_lens_entity)
             .insert(AnimationLens::<T>::new(new_lens_entity));
 
-        fn recurse<T: AnimationLerp>(
-            new_lens: Entity,
-            node: Entity,
-            hierarchy: &Query<&Animations>,
-            conflicts: &Query<Has<DynamicFieldLens<T>>>,
-            mut commands: Commands,
-        ) -> Result {
+        // An explicit stack keeps deep hierarchies from overflowing.
+        let mut stack = vec![new_lens_entity];
+        let mut visited = HashSet::from([new_lens_entity]);
+        while let Some(node) = stack.pop() {
             for child in hierarchy.get(node).ok().iter().flat_map(|a| a.iter()) {
-                if !conflicts.get(child)? {
-                    commands
-                        .entity(child)
-                        .insert(AnimationLens::<T>::new(new_lens));
-                    recurse(new_lens, child, hierarchy, conflicts, commands.reborrow())?;
+                if !visited.insert(child) {
+                    report_cycle(child);
+                    continue;
                 }
-            }
 
-            Ok(())
-        }
+                let (has_lens, existing) = conflicts.get(child)?;
+                if has_lens {
+                    continue;
+                }
+
+                // A closer lens may replace one from further up the hierarchy,
+                // but anything else means two lenses are competing for the node.
+                if let Some(existing) = existing
+                    && existing.get() != new_lens_entity
+                    && !is_ancestor(existing.get(), new_lens_entity, &parents)
+                {
+                    warn!(
+                        "animation node {child} already uses the `DynamicFieldLens<{}>` on {}, \
+                        but {new_lens_entity} is replacing it; check for overlapping lenses",
+                        core::any::type_name::<T>(),
+                        existing.get(),
+                    );
+                }
 
-        recurse(
-            new_lens_entity,
-            new_lens_entity,
-            &hierarchy,
-            &conflicts,
-            commands.reborrow(),
-        )?;
+                commands
+                    .entity(child)
+                    .insert(AnimationLens::<T>::new(new_lens_entity));
+                stack.push(child);
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Whether `ancestor` is above `node` in its animation hierarchy.
+fn is_ancestor(ancestor: Entity, mut node: Entity, parents: &Query<&AnimationOf>) -> bool {
+    let mut visited = HashSet::new();
+    while let Ok(parent) = parents.get(node) {
+        if parent.0 == ancestor {
+            return true;
+        }
+        if !visited.insert(parent.0) {
+            report_cycle(parent.0);
+            break;
+        }
+        node = parent.0;
+    }
+
+    false
+}
+
 #[derive(Component, Clone)]
 #[component(on_add = Self::on_add_hook)]
-pub struct DynamicFieldLens<T: AnimationLerp>(Arc<dyn FieldLens<T>>);
+pub struct DynamicFieldLens<T: AnimationLerp> {
+    lens: Arc<dyn FieldLens<T>>,
+    field: Option<FieldId>,
+}
 
 impl<T: AnimationLerp> FieldLens<T> for DynamicFieldLens<T> {
-    fn get_field(&self, entity: FieldGetter<T>) -> Result<T> {
-        self.0.get_field(entity)
+    fn get_field(&self, entity: FieldAccess<T>) -> Result<T> {
+        self.lens.get_field(entity)
     }
 
-    fn set_field(&self, entity: FieldGetter<T>, value: T) -> Result {
-        self.0.set_field(entity, value)
+    fn set_field(&self, entity: FieldAccess<T>, value: T) -> Result {
+        self.lens.set_field(entity, value)
+    }
+
+    fn modify_field(&self, entity: FieldAccess<T>, modify: &mut dyn FnMut(&mut T)) -> Result {
+        self.lens.modify_field(entity, modify)
     }
 }
 
@@ -128,11 +282,50 @@ where
         FunctionFieldLens::new(lens).into()
     }
 
+    /// Create a lens from a getter and setter, for values that aren't stored
+    /// directly in a field, like a computed property.
+    pub fn from_accessors<G, S, C>(get: G, set: S) -> Self
+    where
+        G: Fn(&C) -> T + Send + Sync + 'static,
+        S: Fn(&mut C, T) + Send + Sync + 'static,
+        C: Component<Mutability = Mutable>,
+    {
+        Self {
+            lens: Arc::new(AccessorFieldLens {
+                get,
+                set,
+                marker: PhantomData,
+            }),
+            field: None,
+        }
+    }
+
+    /// Identify the field this lens targets, allowing it to be blended.
+    pub fn with_field(mut self, field: FieldId) -> Self {
+        self.field = Some(field);
+        self
+    }
+
+    /// The field this lens targets, if known.
+    pub fn field(&self) -> Option<FieldId> {
+        self.field
+    }
+
     fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
-        let mut commands = world.commands();
+        Self::register_systems(&mut world.commands());
+    }
+
+    /// Register the systems every lens of this type relies on.
+    pub(crate) fn register_systems(commands: &mut Commands) {
+        commands.queue(register_node_components::<T>);
         commands.add_systems_dynamic(PreUpdate, || {
             propagate_lens_ref::<T>.before(AnimationSystems::Driver)
         });
+        commands.add_systems_dynamic(Animate, || Modifier::handle_movement::<T>);
+        commands.init_resource::<BlendBuffer<T>>();
+        commands.add_systems_dynamic(PreUpdate, || {
+            resolve_blends::<T>.after(AnimationSystems::Animate)
+        });
     }
 }
 
@@ -143,7 +336,10 @@ where
     P: Clone + Send + Sync + AnimationLerp + 'static,
 {
     fn from(value: FunctionFieldLens<C, P, F>) -> Self {
-        Self(Arc::new(value))
+        Self {
+            lens: Arc::new(value),
+            field: None,
+        }
     }
 }
 
@@ -153,7 +349,7 @@ where
     C: Component<Mutability = Mutable>,
     P: Clone + Send + Sync + AnimationLerp + 'static,
 {
-    fn get_field(&self, mut entity: FieldGetter<P>) -> Result<P> {
+    fn get_field(&self, mut entity: FieldAccess<P>) -> Result<P> {
         let value = entity
             .get_mut::<C>()
             .map(|mut c| (self.func)(&mut c).clone())
@@ -167,7 +363,16 @@ where
         Ok(value)
     }
 
-    fn set_field(&self, mut entity: FieldGetter<P>, value: P) -> Result {
+    fn set_field(&self, entity: FieldAccess<P>, value: P) -> Result {
+        let mut value = Some(value);
+        self.modify_field(entity, &mut |field| {
+            if let Some(value) = value.take() {
+                *field = value;
+            }
+        })
+    }
+
+    fn modify_field(&self, mut entity: FieldAccess<P>, modify: &mut dyn FnMut(&mut P)) -> Result {
         let mut component = entity.get_mut::<C>().ok_or_else(|| {
             format!(
                 "expected component {} on animation target",
@@ -175,7 +380,7 @@ where
             )
         })?;
 
-        *(self.func)(&mut component) = value;
+        modify((self.func)(&mut component));
 
         Ok(())
     }
@@ -201,9 +406,277 @@ where
     }
 }
 
+struct AccessorFieldLens<C, P, G, S> {
+    get: G,
+    set: S,
+    marker: PhantomData<fn(C) -> P>,
+}
+
+impl<C, P, G, S> FieldLens<P> for AccessorFieldLens<C, P, G, S>
+where
+    G: Fn(&C) -> P + Send + Sync + 'static,
+    S: Fn(&mut C, P) + Send + Sync + 'static,
+    C: Component<Mutability = Mutable>,
+    P: AnimationLerp,
+{
+    fn get_field(&self, entity: FieldAccess<P>) -> Result<P> {
+        let value = entity.get::<C>().map(&self.get).ok_or_else(|| {
+            format!(
+                "expected component {} on animation target",
+                core::any::type_name::<C>()
+            )
+        })?;
+
+        Ok(value)
+    }
+
+    fn set_field(&self, mut entity: FieldAccess<P>, value: P) -> Result {
+        let mut component = entity.get_mut::<C>().ok_or_else(|| {
+            format!(
+                "expected component {} on animation target",
+                core::any::type_name::<C>()
+            )
+        })?;
+
+        (self.set)(&mut component, value);
+
+        Ok(())
+    }
+}
+
+/// A lens for `Option<T>` fields.
+///
+/// A `None` field reads as [`AnimationLerp::identity`], and writes to it are
+/// skipped. Since [`Keyframe`] captures its starting value when the playhead
+/// enters it, a field that's `None` at that point animates from the identity
+/// once it becomes `Some`.
+pub struct OptionFieldLens<C, P, F> {
+    func: F,
+    marker: PhantomData<fn(C) -> P>,
+}
+
+impl<C, P, F> OptionFieldLens<C, P, F>
+where
+    F: Fn(&mut C) -> &mut Option<P> + Send + Sync + 'static,
+    C: Component<Mutability = Mutable>,
+    P: AnimationLerp,
+{
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C, P, F> core::fmt::Debug for OptionFieldLens<C, P, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OptionFieldLens")
+            .field("component", &core::any::type_name::<C>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, P, F> FieldLens<P> for OptionFieldLens<C, P, F>
+where
+    F: Fn(&mut C) -> &mut Option<P> + Send + Sync + 'static,
+    C: Component<Mutability = Mutable>,
+    P: AnimationLerp,
+{
+    fn get_field(&self, mut entity: FieldAccess<P>) -> Result<P> {
+        let value = entity
+            .get_mut::<C>()
+            .map(|mut c| (self.func)(&mut c).clone())
+            .ok_or_else(|| {
+                format!(
+                    "expected component {} on animation target",
+                    core::any::type_name::<C>()
+                )
+            })?;
+
+        Ok(value.unwrap_or_else(P::identity))
+    }
+
+    fn set_field(&self, entity: FieldAccess<P>, value: P) -> Result {
+        let mut value = Some(value);
+        self.modify_field(entity, &mut |field| {
+            if let Some(value) = value.take() {
+                *field = value;
+            }
+        })
+    }
+
+    fn modify_field(&self, mut entity: FieldAccess<P>, modify: &mut dyn FnMut(&mut P)) -> Result {
+        let mut component = entity.get_mut::<C>().ok_or_else(|| {
+            format!(
+                "expected component {} on animation target",
+                core::any::type_name::<C>()
+            )
+        })?;
+
+        if let Some(field) = (self.func)(&mut component) {
+            modify(field);
+        }
+
+        Ok(())
+    }
+}
+
+impl<C, P, F> From<OptionFieldLens<C, P, F>> for DynamicFieldLens<P>
+where
+    F: Fn(&mut C) -> &mut Option<P> + Send + Sync + 'static,
+    C: Component<Mutability = Mutable>,
+    P: AnimationLerp,
+{
+    fn from(value: OptionFieldLens<C, P, F>) -> Self {
+        Self {
+            lens: Arc::new(value),
+            field: None,
+        }
+    }
+}
+
+/// A lens that resolves a field by its reflection path at runtime.
+///
+/// The path begins with the component's type path (or short type path),
+/// followed by the path to the field within the component,
+/// like `"Transform.translation.x"`.
+pub struct ReflectFieldLens<T> {
+    component: ReflectComponent,
+    type_path: &'static str,
+    path: ParsedPath,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> core::fmt::Debug for ReflectFieldLens<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReflectFieldLens")
+            .field("type_path", &self.type_path)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: AnimationLerp> ReflectFieldLens<T> {
+    /// Resolve the component in `path` from the type registry.
+    pub fn new(registry: &TypeRegistry, path: &str) -> Result<Self> {
+        let (component, field) = path
+            .split_once('.')
+            .ok_or_else(|| format!("expected a path like \"Component.field\", found \"{path}\""))?;
+
+        let registration = registry
+            .get_with_short_type_path(component)
+            .or_else(|| registry.get_with_type_path(component))
+            .ok_or_else(|| format!("no type named \"{component}\" is registered"))?;
+
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or_else(|| format!("\"{component}\" does not reflect `Component`"))?
+            .clone();
+
+        let field = ParsedPath::parse(field)
+            .map_err(|e| format!("invalid field path in \"{path}\": {e}"))?;
+
+        Ok(Self {
+            component: reflect_component,
+            type_path: registration.type_info().type_path(),
+            path: field,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: AnimationLerp> FieldLens<T> for ReflectFieldLens<T> {
+    fn get_field(&self, entity: FieldAccess<T>) -> Result<T> {
+        let entity = EntityRefExcept::from(&entity.0);
+        let component = self
+            .component
+            .reflect(&entity)
+            .ok_or_else(|| format!("expected component {} on animation target", self.type_path))?;
+
+        let field = component
+            .reflect_path(&self.path)
+            .map_err(|e| format!("failed to resolve field on {}: {e}", self.type_path))?;
+
+        let value = field.try_downcast_ref::<T>().ok_or_else(|| {
+            format!(
+                "expected field {} on {} to be {}, found {}",
+                self.path,
+                self.type_path,
+                core::any::type_name::<T>(),
+                field.reflect_type_path()
+            )
+        })?;
+
+        Ok(value.clone())
+    }
+
+    fn set_field(&self, entity: FieldAccess<T>, value: T) -> Result {
+        let mut value = Some(value);
+        self.modify_field(entity, &mut |field| {
+            if let Some(value) = value.take() {
+                *field = value;
+            }
+        })
+    }
+
+    fn modify_field(&self, entity: FieldAccess<T>, modify: &mut dyn FnMut(&mut T)) -> Result {
+        let mut component = self
+            .component
+            .reflect_mut(&entity.0)
+            .ok_or_else(|| format!("expected component {} on animation target", self.type_path))?;
+
+        let field = component
+            .reflect_path_mut(&self.path)
+            .map_err(|e| format!("failed to resolve field on {}: {e}", self.type_path))?;
+
+        match field.try_downcast_mut::<T>() {
+            Some(field) => modify(field),
+            None => {
+                return Err(format!(
+                    "expected field {} on {} to be {}, found {}",
+                    self.path,
+                    self.type_path,
+                    core::any::type_name::<T>(),
+                    field.reflect_type_path()
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: AnimationLerp> From<ReflectFieldLens<T>> for DynamicFieldLens<T> {
+    fn from(value: ReflectFieldLens<T>) -> Self {
+        Self {
+            lens: Arc::new(value),
+            field: None,
+        }
+    }
+}
+
+/// Create a [`DynamicFieldLens`] for a component's field.
+///
+/// The field may be a dotted path through nested fields or tuple indices,
+/// like `lens!(Transform::translation.x)` or `lens!(TextColor::0)`,
+/// as long as the field at the end implements [`AnimationLerp`].
+///
+/// Prefixing an entity, like `lens!(mesh => Transform::translation)`,
+/// also sets the node's [`AnimationTarget`] to that entity.
 #[macro_export]
 macro_rules! lens {
-    ($component:ident::$field:tt) => {
-        $crate::DynamicFieldLens::new(|component: &mut $component| &mut component.$field)
+    ($component:ident::$field:tt $(.$rest:tt)*) => {
+        $crate::DynamicFieldLens::new(|component: &mut $component| {
+            &mut component.$field$(.$rest)*
+        })
+        .with_field($crate::blend_field!($component::$field $(.$rest)*))
+    };
+    ($target:expr => $component:ident::$field:tt $(.$rest:tt)*) => {
+        (
+            $crate::AnimationTarget($target),
+            $crate::lens!($component::$field $(.$rest)*),
+        )
     };
 }
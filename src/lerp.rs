@@ -1,14 +1,107 @@
 // use firewheel::Volume;
-use bevy_color::{Color, Mix};
-use bevy_math::prelude::*;
+use crate::BlendMode;
+use bevy_color::{Color, Hsla, LinearRgba, Mix, Srgba};
+use bevy_log::warn_once;
+use bevy_math::{Affine3A, prelude::*};
+use bevy_transform::components::Transform;
+use core::{
+    ops::{Add, Sub},
+    time::Duration,
+};
+
+pub trait AnimationLerp: Clone + Send + Sync + 'static {
+    /// The neutral value that relative animations, like `Delta`, start from.
+    ///
+    /// For most types, this is zero.
+    fn identity() -> Self;
 
-pub trait AnimationLerp: Default + Clone + Send + Sync + 'static {
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self;
+
+    /// Interpolate into an existing value.
+    ///
+    /// By default, this overwrites `out` with [`AnimationLerp::animation_lerp`].
+    /// Large types can override it to reuse `out`'s allocations.
+    fn animation_lerp_into(&self, other: &Self, amount: f32, out: &mut Self) {
+        *out = self.animation_lerp(other, amount);
+    }
+
     fn difference(&self, other: &Self) -> Self;
     fn accumulate(&mut self, value: &Self);
+
+    /// Multiply the value by a factor.
+    ///
+    /// By default, this interpolates from [`AnimationLerp::identity`] to the value,
+    /// which is correct for types whose identity is zero.
+    fn scale(&self, factor: f32) -> Self {
+        Self::identity().animation_lerp(self, factor)
+    }
+
+    /// Combine the values of several concurrent animations.
+    ///
+    /// By default, only [`BlendMode::Sum`] and [`BlendMode::Mean`] are supported.
+    /// Returns `None` if there are no values or the mode isn't supported.
+    fn blend(values: &[Self], mode: BlendMode) -> Option<Self> {
+        let (first, rest) = values.split_first()?;
+
+        let mut sum = first.clone();
+        for value in rest {
+            sum.accumulate(value);
+        }
+
+        match mode {
+            BlendMode::Sum => Some(sum),
+            BlendMode::Mean => Some(sum.scale(1.0 / values.len() as f32)),
+            _ => None,
+        }
+    }
+}
+
+/// A type whose differences are plain subtraction and addition.
+///
+/// Implementing this provides [`AnimationLerp`], so only the interpolation
+/// needs to be written. The identity is the type's [`Default`], which should
+/// be zero.
+///
+/// ```ignore
+/// // `Meters` also implements `Add` and `Sub`.
+/// #[derive(Clone, Copy, Default)]
+/// struct Meters(f32);
+///
+/// impl AdditiveLerp for Meters {
+///     fn interpolate(&self, other: &Self, amount: f32) -> Self {
+///         Meters(self.0.lerp(other.0, amount))
+///     }
+/// }
+/// ```
+pub trait AdditiveLerp:
+    Clone + Default + Add<Output = Self> + Sub<Output = Self> + Send + Sync + 'static
+{
+    fn interpolate(&self, other: &Self, amount: f32) -> Self;
+}
+
+impl<T: AdditiveLerp> AnimationLerp for T {
+    fn identity() -> Self {
+        T::default()
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.interpolate(other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        self.clone() - other.clone()
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self = self.clone() + value.clone();
+    }
 }
 
 impl AnimationLerp for f32 {
+    fn identity() -> Self {
+        0.0
+    }
+
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
         self.lerp(*other, amount)
     }
@@ -20,65 +113,304 @@ impl AnimationLerp for f32 {
     fn accumulate(&mut self, value: &Self) {
         *self += *value;
     }
+
+    fn blend(values: &[Self], mode: BlendMode) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let count = values.len() as f32;
+        let values = values.iter().copied();
+        Some(match mode {
+            BlendMode::Mean => values.sum::<f32>() / count,
+            BlendMode::Min => values.fold(f32::INFINITY, f32::min),
+            BlendMode::Max => values.fold(f32::NEG_INFINITY, f32::max),
+            BlendMode::Sum => values.sum(),
+            BlendMode::Product => values.product(),
+        })
+    }
 }
 
-impl AnimationLerp for f64 {
-    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+impl AdditiveLerp for f64 {
+    fn interpolate(&self, other: &Self, amount: f32) -> Self {
         self.lerp(*other, amount as f64)
     }
+}
+
+// Integers round to the nearest value, so for stepped animations like sprite
+// indices, pair them with `AnimationCurve::steps`. Differences wrap, which still
+// accumulates correctly when the difference is negative.
+impl AnimationLerp for u32 {
+    fn identity() -> Self {
+        0
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        (*self as f64).lerp(*other as f64, amount as f64).round() as u32
+    }
 
     fn difference(&self, other: &Self) -> Self {
-        *self - *other
+        self.wrapping_sub(*other)
     }
 
     fn accumulate(&mut self, value: &Self) {
-        *self += *value;
+        *self = self.wrapping_add(*value);
     }
 }
 
-impl AnimationLerp for Vec2 {
+impl AnimationLerp for usize {
+    fn identity() -> Self {
+        0
+    }
+
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
-        self.lerp(*other, amount)
+        (*self as f64).lerp(*other as f64, amount as f64).round() as usize
     }
 
     fn difference(&self, other: &Self) -> Self {
-        *self - *other
+        self.wrapping_sub(*other)
     }
 
     fn accumulate(&mut self, value: &Self) {
-        *self += *value;
+        *self = self.wrapping_add(*value);
     }
 }
 
-impl AnimationLerp for Vec3 {
+/// Durations interpolate their seconds, saturating at zero, so timers
+/// and cooldowns can be keyframed.
+///
+/// A duration can't be negative, so a [`difference`](AnimationLerp::difference)
+/// towards a shorter duration is zero. Relative animations like `Delta`
+/// can only lengthen durations. Use [`Seconds`] to shorten them too.
+impl AnimationLerp for Duration {
+    fn identity() -> Self {
+        Duration::ZERO
+    }
+
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
-        self.lerp(*other, amount)
+        let seconds = self
+            .as_secs_f64()
+            .lerp(other.as_secs_f64(), amount as f64)
+            .max(0.0);
+        Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
     }
 
     fn difference(&self, other: &Self) -> Self {
-        *self - *other
+        self.saturating_sub(*other)
     }
 
     fn accumulate(&mut self, value: &Self) {
-        *self += *value;
+        *self = self.saturating_add(*value);
     }
 }
 
-impl AnimationLerp for Quat {
+/// A signed span of time in seconds, independent of any clock.
+///
+/// Unlike [`Duration`], this can be negative, so its differences are
+/// exact in both directions and a `Delta` can shorten it as well as
+/// lengthen it. Convert from and to a [`Duration`] with [`From`] and
+/// [`Seconds::duration`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Seconds(pub f32);
+
+impl Seconds {
+    /// The span as a [`Duration`], saturating negative spans to zero.
+    pub fn duration(self) -> Duration {
+        Duration::try_from_secs_f32(self.0.max(0.0)).unwrap_or(Duration::MAX)
+    }
+}
+
+impl From<Duration> for Seconds {
+    fn from(duration: Duration) -> Self {
+        Self(duration.as_secs_f32())
+    }
+}
+
+impl AnimationLerp for Seconds {
+    fn identity() -> Self {
+        Self(0.0)
+    }
+
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        Self(self.0.lerp(other.0, amount))
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Self(self.0 - other.0)
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.0 += value.0;
+    }
+}
+
+impl AdditiveLerp for Vec2 {
+    fn interpolate(&self, other: &Self, amount: f32) -> Self {
         self.lerp(*other, amount)
     }
+}
+
+impl AdditiveLerp for Vec3 {
+    fn interpolate(&self, other: &Self, amount: f32) -> Self {
+        self.lerp(*other, amount)
+    }
+}
+
+impl AdditiveLerp for Vec3A {
+    fn interpolate(&self, other: &Self, amount: f32) -> Self {
+        self.lerp(*other, amount)
+    }
+}
+
+impl AdditiveLerp for Vec4 {
+    fn interpolate(&self, other: &Self, amount: f32) -> Self {
+        self.lerp(*other, amount)
+    }
+}
+
+// Rotations interpolate with `slerp` for a constant angular velocity. A
+// difference is the rotation taking `other` to `self`, so accumulating it
+// onto `other` gives `self` back, and opposite differences cancel out.
+impl AnimationLerp for Quat {
+    fn identity() -> Self {
+        Quat::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.slerp(*other, amount)
+    }
 
     fn difference(&self, other: &Self) -> Self {
-        *other * self.inverse()
+        *self * other.inverse()
     }
 
     fn accumulate(&mut self, value: &Self) {
-        *self = *value * *self;
+        *self = (*value * *self).normalize();
+    }
+}
+
+// Rotations interpolate along the shortest arc, so animating from 350° to 10°
+// turns 20° rather than 340°. Differences are wrapped the same way.
+impl AnimationLerp for Rot2 {
+    fn identity() -> Self {
+        Rot2::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.slerp(*other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        *self * other.inverse()
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self = (*value * *self).fast_renormalize();
+    }
+}
+
+// Directions have no zero, so differences are stored as the rotation
+// of the X axis. Both `slerp`s are well-defined for antipodal directions.
+impl AnimationLerp for Dir2 {
+    fn identity() -> Self {
+        Dir2::X
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.slerp(*other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        self.rotation_from(*other) * Dir2::X
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self = (value.rotation_from_x() * *self).fast_renormalize();
+    }
+}
+
+/// A [`Dir3`] difference is the direction the X axis is rotated to, which
+/// can't hold a full rotation. Accumulating applies the shortest rotation
+/// from the X axis to the difference, so any twist around the X axis is lost.
+///
+/// Differences are exact relative to [`Dir3::X`], the identity, and for any
+/// rotation that moves the X axis along its shortest arc. Other rotations,
+/// like a quarter turn from [`Dir3::Y`] to [`Dir3::Z`], which leaves the X
+/// axis in place, accumulate to no change at all.
+impl AnimationLerp for Dir3 {
+    fn identity() -> Self {
+        Dir3::X
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.slerp(*other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Quat::from_rotation_arc(other.as_vec3(), self.as_vec3()) * Dir3::X
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        let rotation = Quat::from_rotation_arc(Vec3::X, value.as_vec3());
+        *self = (rotation * *self).fast_renormalize();
+    }
+}
+
+impl AnimationLerp for Isometry2d {
+    fn identity() -> Self {
+        Isometry2d::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        Isometry2d {
+            rotation: self.rotation.slerp(other.rotation, amount),
+            translation: self.translation.animation_lerp(&other.translation, amount),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Isometry2d {
+            rotation: self.rotation * other.rotation.inverse(),
+            translation: self.translation.difference(&other.translation),
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.rotation = (value.rotation * self.rotation).fast_renormalize();
+        self.translation.accumulate(&value.translation);
+    }
+}
+
+impl AnimationLerp for Isometry3d {
+    fn identity() -> Self {
+        Isometry3d::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        Isometry3d {
+            rotation: self.rotation.slerp(other.rotation, amount),
+            translation: self.translation.animation_lerp(&other.translation, amount),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Isometry3d {
+            rotation: self.rotation.difference(&other.rotation),
+            translation: self.translation.difference(&other.translation),
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.rotation.accumulate(&value.rotation);
+        self.translation.accumulate(&value.translation);
     }
 }
 
 impl AnimationLerp for Color {
+    fn identity() -> Self {
+        Color::from(bevy_color::Oklaba::new(0.0, 0.0, 0.0, 0.0))
+    }
+
     fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
         self.mix(other, amount)
     }
@@ -96,8 +428,351 @@ impl AnimationLerp for Color {
 
         *self = Color::from(a + b)
     }
+
+    fn scale(&self, factor: f32) -> Self {
+        Color::from(bevy_color::Oklaba::from(*self) * factor)
+    }
+}
+
+// Unlike `Color`, these operate component-wise in their own space.
+impl AnimationLerp for LinearRgba {
+    fn identity() -> Self {
+        LinearRgba::NONE
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.mix(other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self += *value;
+    }
+}
+
+impl AnimationLerp for Srgba {
+    fn identity() -> Self {
+        Srgba::NONE
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.mix(other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        *self - *other
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self += *value;
+    }
+}
+
+/// Hue differences take the shortest path around the color wheel.
+fn hue_difference(a: f32, b: f32) -> f32 {
+    (a - b + 180.0).rem_euclid(360.0) - 180.0
+}
+
+impl AnimationLerp for Hsla {
+    fn identity() -> Self {
+        Hsla::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        self.mix(other, amount)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Hsla {
+            hue: hue_difference(self.hue, other.hue),
+            saturation: self.saturation - other.saturation,
+            lightness: self.lightness - other.lightness,
+            alpha: self.alpha - other.alpha,
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.hue = (self.hue + value.hue).rem_euclid(360.0);
+        self.saturation += value.saturation;
+        self.lightness += value.lightness;
+        self.alpha += value.alpha;
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        Hsla {
+            hue: self.hue * factor,
+            saturation: self.saturation * factor,
+            lightness: self.lightness * factor,
+            alpha: self.alpha * factor,
+        }
+    }
+}
+
+impl AnimationLerp for Transform {
+    fn identity() -> Self {
+        Transform::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        Transform {
+            translation: self.translation.animation_lerp(&other.translation, amount),
+            rotation: self.rotation.animation_lerp(&other.rotation, amount),
+            scale: self.scale.animation_lerp(&other.scale, amount),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Transform {
+            translation: self.translation.difference(&other.translation),
+            rotation: self.rotation.difference(&other.rotation),
+            scale: self.scale.difference(&other.scale),
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.translation.accumulate(&value.translation);
+        self.rotation.accumulate(&value.rotation);
+        self.scale.accumulate(&value.scale);
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        Transform {
+            translation: self.translation * factor,
+            rotation: self.rotation.scale(factor),
+            scale: self.scale * factor,
+        }
+    }
+}
+
+/// Matrices interpolate their decomposed translation, rotation, and scale,
+/// like [`Transform`], so rotations don't shear partway through.
+///
+/// Matrices with shear or projection can't be decomposed, so they fall back
+/// to interpolating each element and log a warning. Differences compose by
+/// multiplication, so a `Delta` of a matrix applies it on top of the target.
+impl AnimationLerp for Mat4 {
+    fn identity() -> Self {
+        Mat4::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        match (decompose(self), decompose(other)) {
+            (Some(a), Some(b)) => a.animation_lerp(&b, amount).compute_matrix(),
+            _ => {
+                warn_once!("cannot decompose a matrix for animation; interpolating its elements");
+                *self * (1.0 - amount) + *other * amount
+            }
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        *self * other.inverse()
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self = *value * *self;
+    }
+}
+
+impl AnimationLerp for Affine3A {
+    fn identity() -> Self {
+        Affine3A::IDENTITY
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        let matrix = Mat4::from(*self).animation_lerp(&Mat4::from(*other), amount);
+        Affine3A::from_mat4(matrix)
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        *self * other.inverse()
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        *self = *value * *self;
+    }
+}
+
+/// Split an affine matrix into a [`Transform`], if that reproduces it.
+fn decompose(matrix: &Mat4) -> Option<Transform> {
+    if matrix.row(3) != Vec4::W || matrix.determinant().abs() <= f32::EPSILON {
+        return None;
+    }
+
+    let transform = Transform::from_matrix(*matrix);
+    let magnitude = matrix.abs().to_cols_array().into_iter().fold(1.0, f32::max);
+    transform
+        .compute_matrix()
+        .abs_diff_eq(*matrix, magnitude * 1e-4)
+        .then_some(transform)
+}
+
+impl AnimationLerp for Rect {
+    fn identity() -> Self {
+        Rect::from_corners(Vec2::ZERO, Vec2::ZERO)
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        Rect {
+            min: self.min.animation_lerp(&other.min, amount),
+            max: self.max.animation_lerp(&other.max, amount),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Rect {
+            min: self.min - other.min,
+            max: self.max - other.max,
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        self.min += value.min;
+        self.max += value.max;
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        Rect {
+            min: self.min * factor,
+            max: self.max * factor,
+        }
+    }
 }
 
+// Vectors animate element-wise. An empty vector is the identity, so it
+// pairs with any length. Otherwise, vectors of different lengths can't be
+// interpolated: the start holds until the end of the interpolation, and
+// differences and accumulation are skipped.
+impl<T: AnimationLerp> AnimationLerp for Vec<T> {
+    fn identity() -> Self {
+        Vec::new()
+    }
+
+    fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+        let mut out = Vec::new();
+        self.animation_lerp_into(other, amount, &mut out);
+        out
+    }
+
+    fn animation_lerp_into(&self, other: &Self, amount: f32, out: &mut Self) {
+        match (self.len(), other.len()) {
+            // Matching lengths interpolate into `out`'s existing elements.
+            (a, b) if a == b => {
+                out.truncate(a);
+                let reused = out.len();
+                for ((a, b), out) in self.iter().zip(other).zip(out.iter_mut()) {
+                    a.animation_lerp_into(b, amount, out);
+                }
+
+                out.extend(
+                    self[reused..]
+                        .iter()
+                        .zip(&other[reused..])
+                        .map(|(a, b)| a.animation_lerp(b, amount)),
+                );
+            }
+            (0, _) => {
+                out.clear();
+                out.extend(
+                    other
+                        .iter()
+                        .map(|b| T::identity().animation_lerp(b, amount)),
+                );
+            }
+            (_, 0) => {
+                out.clear();
+                out.extend(
+                    self.iter()
+                        .map(|a| a.animation_lerp(&T::identity(), amount)),
+                );
+            }
+            (a, b) => {
+                length_mismatch(a, b);
+                let held = if amount < 1.0 { self } else { other };
+                out.clone_from(held);
+            }
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        match (self.len(), other.len()) {
+            (a, b) if a == b => self
+                .iter()
+                .zip(other)
+                .map(|(a, b)| a.difference(b))
+                .collect(),
+            (_, 0) => self.clone(),
+            (0, _) => other.iter().map(|b| T::identity().difference(b)).collect(),
+            (a, b) => {
+                length_mismatch(a, b);
+                Vec::new()
+            }
+        }
+    }
+
+    fn accumulate(&mut self, value: &Self) {
+        match (self.len(), value.len()) {
+            (_, 0) => {}
+            (0, _) => self.clone_from(value),
+            (a, b) if a == b => {
+                for (a, b) in self.iter_mut().zip(value) {
+                    a.accumulate(b);
+                }
+            }
+            (a, b) => length_mismatch(a, b),
+        }
+    }
+
+    fn scale(&self, factor: f32) -> Self {
+        self.iter().map(|value| value.scale(factor)).collect()
+    }
+}
+
+fn length_mismatch(a: usize, b: usize) {
+    warn_once!("cannot animate between vectors of length {a} and {b}");
+}
+
+// Tuples animate component-wise.
+macro_rules! impl_tuple {
+    ($($name:ident $index:tt),*) => {
+        impl<$($name: AnimationLerp),*> AnimationLerp for ($($name,)*) {
+            fn identity() -> Self {
+                ($($name::identity(),)*)
+            }
+
+            fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+                ($(self.$index.animation_lerp(&other.$index, amount),)*)
+            }
+
+            fn animation_lerp_into(&self, other: &Self, amount: f32, out: &mut Self) {
+                $(self.$index.animation_lerp_into(&other.$index, amount, &mut out.$index);)*
+            }
+
+            fn difference(&self, other: &Self) -> Self {
+                ($(self.$index.difference(&other.$index),)*)
+            }
+
+            fn accumulate(&mut self, value: &Self) {
+                $(self.$index.accumulate(&value.$index);)*
+            }
+
+            fn scale(&self, factor: f32) -> Self {
+                ($(self.$index.scale(factor),)*)
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+
+#[cfg(feature = "firewheel")]
+pub use firewheel::Pan;
+
 #[cfg(feature = "firewheel")]
 mod firewheel {
     use super::AnimationLerp;
@@ -112,7 +787,55 @@ mod firewheel {
         if db < -96.0 { -96.0 } else { db }
     }
 
+    /// A stereo pan, from `-1.0` (fully left) to `1.0` (fully right).
+    ///
+    /// Firewheel stores pan parameters, like the `pan` of its volume-pan node,
+    /// as plain `f32`s. Animating them through this type keeps them in range,
+    /// even when a `Delta` or an overshooting curve would push them past it.
+    ///
+    /// ```ignore
+    /// DynamicFieldLens::from_accessors(
+    ///     |node: &VolumePanNode| Pan(node.pan),
+    ///     |node: &mut VolumePanNode, pan: Pan| node.pan = pan.0,
+    /// )
+    /// ```
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    pub struct Pan(pub f32);
+
+    impl Pan {
+        pub const LEFT: Self = Self(-1.0);
+        pub const CENTER: Self = Self(0.0);
+        pub const RIGHT: Self = Self(1.0);
+
+        fn clamped(pan: f32) -> Self {
+            Self(pan.clamp(-1.0, 1.0))
+        }
+    }
+
+    // Differences aren't clamped, so a delta can sweep the full range.
+    impl AnimationLerp for Pan {
+        fn identity() -> Self {
+            Self::CENTER
+        }
+
+        fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+            Self::clamped(self.0.lerp(other.0, amount))
+        }
+
+        fn difference(&self, other: &Self) -> Self {
+            Self(self.0 - other.0)
+        }
+
+        fn accumulate(&mut self, value: &Self) {
+            *self = Self::clamped(self.0 + value.0);
+        }
+    }
+
     impl AnimationLerp for Volume {
+        fn identity() -> Self {
+            Self::default()
+        }
+
         fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
             match (self, other) {
                 (Self::Linear(a), Self::Linear(b)) => Self::Linear(a.animation_lerp(b, amount)),
@@ -147,9 +870,20 @@ mod firewheel {
 
             *self = value;
         }
+
+        fn scale(&self, factor: f32) -> Self {
+            match *self {
+                Self::Linear(a) => Self::Linear(a * factor),
+                Self::Decibels(db) => Self::Decibels(clamp(db + 20.0 * factor.log10())),
+            }
+        }
     }
 
     impl AnimationLerp for InstantSeconds {
+        fn identity() -> Self {
+            Self::default()
+        }
+
         fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
             Self(self.0.lerp(other.0, amount as f64))
         }
@@ -164,6 +898,10 @@ mod firewheel {
     }
 
     impl AnimationLerp for InstantMusical {
+        fn identity() -> Self {
+            Self::default()
+        }
+
         fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
             Self(self.0.lerp(other.0, amount as f64))
         }
@@ -178,6 +916,10 @@ mod firewheel {
     }
 
     impl<T: AnimationLerp> AnimationLerp for Notify<T> {
+        fn identity() -> Self {
+            Notify::new(T::identity())
+        }
+
         fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
             Notify::new(self.as_ref().animation_lerp(other.as_ref(), amount))
         }
@@ -191,3 +933,274 @@ mod firewheel {
         }
     }
 }
+
+#[cfg(feature = "bevy_ui")]
+mod ui {
+    use super::AnimationLerp;
+    use bevy_log::warn_once;
+    use bevy_math::FloatExt;
+    use bevy_ui::{UiRect, Val};
+
+    /// Split a value into its unit and magnitude.
+    fn parts(val: Val) -> Option<(fn(f32) -> Val, f32)> {
+        match val {
+            Val::Auto => None,
+            Val::Px(v) => Some((Val::Px, v)),
+            Val::Percent(v) => Some((Val::Percent, v)),
+            Val::Vw(v) => Some((Val::Vw, v)),
+            Val::Vh(v) => Some((Val::Vh, v)),
+            Val::VMin(v) => Some((Val::VMin, v)),
+            Val::VMax(v) => Some((Val::VMax, v)),
+        }
+    }
+
+    /// Combine two values of the same unit.
+    ///
+    /// Zero is treated as unitless, so values can start from [`Val::ZERO`].
+    /// Returns `None` for `Auto` or mismatched units.
+    fn combine(a: Val, b: Val, f: impl Fn(f32, f32) -> f32) -> Option<Val> {
+        let (unit_a, a) = parts(a)?;
+        let (unit_b, b) = parts(b)?;
+
+        let unit = if a == 0.0 {
+            unit_b
+        } else if b == 0.0 || unit_a(1.0) == unit_b(1.0) {
+            unit_a
+        } else {
+            return None;
+        };
+
+        Some(unit(f(a, b)))
+    }
+
+    fn mismatch(a: Val, b: Val) {
+        warn_once!("cannot animate between {a:?} and {b:?}; values must share a unit");
+    }
+
+    // Values can only be interpolated within a single unit, since
+    // the size of a pixel relative to a percent isn't known here.
+    // Mismatched values hold until the end of the interpolation.
+    impl AnimationLerp for Val {
+        fn identity() -> Self {
+            Val::ZERO
+        }
+
+        fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+            match combine(*self, *other, |a, b| a.lerp(b, amount)) {
+                Some(value) => value,
+                None if self == other => *self,
+                None => {
+                    mismatch(*self, *other);
+                    if amount < 1.0 { *self } else { *other }
+                }
+            }
+        }
+
+        fn difference(&self, other: &Self) -> Self {
+            match combine(*self, *other, |a, b| a - b) {
+                Some(value) => value,
+                None if self == other => Val::ZERO,
+                None => {
+                    mismatch(*self, *other);
+                    Val::ZERO
+                }
+            }
+        }
+
+        fn accumulate(&mut self, value: &Self) {
+            match combine(*self, *value, |a, b| a + b) {
+                Some(sum) => *self = sum,
+                None => mismatch(*self, *value),
+            }
+        }
+
+        fn scale(&self, factor: f32) -> Self {
+            match parts(*self) {
+                Some((unit, value)) => unit(value * factor),
+                None => *self,
+            }
+        }
+    }
+
+    impl AnimationLerp for UiRect {
+        fn identity() -> Self {
+            UiRect::ZERO
+        }
+
+        fn animation_lerp(&self, other: &Self, amount: f32) -> Self {
+            UiRect {
+                left: self.left.animation_lerp(&other.left, amount),
+                right: self.right.animation_lerp(&other.right, amount),
+                top: self.top.animation_lerp(&other.top, amount),
+                bottom: self.bottom.animation_lerp(&other.bottom, amount),
+            }
+        }
+
+        fn difference(&self, other: &Self) -> Self {
+            UiRect {
+                left: self.left.difference(&other.left),
+                right: self.right.difference(&other.right),
+                top: self.top.difference(&other.top),
+                bottom: self.bottom.difference(&other.bottom),
+            }
+        }
+
+        fn accumulate(&mut self, value: &Self) {
+            self.left.accumulate(&value.left);
+            self.right.accumulate(&value.right);
+            self.top.accumulate(&value.top);
+            self.bottom.accumulate(&value.bottom);
+        }
+
+        fn scale(&self, factor: f32) -> Self {
+            UiRect {
+                left: self.left.scale(factor),
+                right: self.right.scale(factor),
+                top: self.top.scale(factor),
+                bottom: self.bottom.scale(factor),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_dir_eq(a: Dir3, b: Dir3) {
+        assert!(a.angle_between(*b) < 1e-4, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn dir3_differences_round_trip_from_the_identity() {
+        let identity = Dir3::identity();
+        for dir in [
+            Dir3::Y,
+            Dir3::NEG_Z,
+            Dir3::NEG_X,
+            Dir3::new(Vec3::ONE).unwrap(),
+        ] {
+            let mut accumulated = identity;
+            accumulated.accumulate(&dir.difference(&identity));
+            assert_dir_eq(accumulated, dir);
+        }
+    }
+
+    #[test]
+    fn dir3_differences_lose_twist_around_x() {
+        let mut accumulated = Dir3::Y;
+        accumulated.accumulate(&Dir3::Z.difference(&Dir3::Y));
+        assert_dir_eq(accumulated, Dir3::Y);
+    }
+
+    #[test]
+    fn duration_differences_saturate_but_seconds_are_signed() {
+        let short = Duration::from_secs(1);
+        let long = Duration::from_secs(3);
+        assert_eq!(short.difference(&long), Duration::ZERO);
+
+        let mut shortened = long;
+        shortened.accumulate(&short.difference(&long));
+        assert_eq!(shortened, long);
+
+        let mut shortened = Seconds::from(long);
+        shortened.accumulate(&Seconds::from(short).difference(&Seconds::from(long)));
+        assert_eq!(shortened.duration(), short);
+        assert_eq!(Seconds(-1.0).duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn quat_turns_at_a_constant_rate() {
+        let start = Quat::IDENTITY;
+        let end = Quat::from_rotation_z(170f32.to_radians());
+
+        let steps = 10;
+        for i in 0..steps {
+            let a = start.animation_lerp(&end, i as f32 / steps as f32);
+            let b = start.animation_lerp(&end, (i + 1) as f32 / steps as f32);
+            let step = a.angle_between(b).to_degrees();
+            assert!((step - 17.0).abs() < 1e-2, "step {i} turned {step}°");
+        }
+    }
+
+    #[test]
+    fn vecs_of_equal_length_animate_element_wise() {
+        let start = vec![Vec2::ZERO, Vec2::new(2.0, 4.0)];
+        let end = vec![Vec2::new(2.0, -2.0), Vec2::new(4.0, 4.0)];
+
+        assert_eq!(
+            start.animation_lerp(&end, 0.5),
+            [Vec2::new(1.0, -1.0), Vec2::new(3.0, 4.0)]
+        );
+
+        let difference = end.difference(&start);
+        assert_eq!(difference, [Vec2::new(2.0, -2.0), Vec2::new(2.0, 0.0)]);
+
+        let mut accumulated = start.clone();
+        accumulated.accumulate(&difference);
+        assert_eq!(accumulated, end);
+
+        // The empty identity pairs with any length.
+        let mut accumulated = Vec::identity();
+        accumulated.accumulate(&end.difference(&Vec::identity()));
+        assert_eq!(accumulated, end);
+    }
+
+    #[test]
+    fn vecs_of_unequal_length_hold_the_start() {
+        let start = vec![Vec2::ZERO, Vec2::ONE];
+        let end = vec![Vec2::ONE, Vec2::ZERO, Vec2::ONE];
+
+        for amount in [0.0, 0.5, 0.99] {
+            assert_eq!(start.animation_lerp(&end, amount), start);
+        }
+        assert_eq!(start.animation_lerp(&end, 1.0), end);
+
+        assert!(end.difference(&start).is_empty());
+
+        let mut accumulated = start.clone();
+        accumulated.accumulate(&end);
+        assert_eq!(accumulated, start);
+    }
+
+    #[test]
+    fn vec_lerp_into_reuses_elements() {
+        let start = vec![vec![0.0, 0.0], vec![2.0]];
+        let end = vec![vec![1.0, 2.0], vec![4.0]];
+        let mut out = vec![Vec::with_capacity(8), Vec::with_capacity(8)];
+        let buffers: Vec<_> = out.iter().map(Vec::as_ptr).collect();
+
+        start.animation_lerp_into(&end, 0.5, &mut out);
+
+        assert_eq!(out, [vec![0.5, 1.0], vec![3.0]]);
+        assert_eq!(out.iter().map(Vec::as_ptr).collect::<Vec<_>>(), buffers);
+    }
+
+    #[cfg(feature = "firewheel")]
+    #[test]
+    fn pan_stays_in_range() {
+        assert_eq!(Pan::LEFT.animation_lerp(&Pan::RIGHT, 0.5), Pan::CENTER);
+        assert_eq!(Pan::LEFT.animation_lerp(&Pan::RIGHT, 1.5), Pan::RIGHT);
+
+        let difference = Pan::RIGHT.difference(&Pan::LEFT);
+        assert_eq!(difference, Pan(2.0));
+
+        let mut pan = Pan(0.5);
+        pan.accumulate(&difference);
+        assert_eq!(pan, Pan::RIGHT);
+    }
+
+    #[test]
+    fn antipodal_dir3_stays_finite() {
+        let difference = Dir3::NEG_X.difference(&Dir3::X);
+        assert!(difference.is_finite());
+
+        let mut accumulated = Dir3::X;
+        accumulated.accumulate(&difference);
+        assert_dir_eq(accumulated, Dir3::NEG_X);
+
+        for amount in [0.0, 0.5, 1.0] {
+            assert!(Dir3::X.animation_lerp(&Dir3::NEG_X, amount).is_finite());
+        }
+    }
+}
@@ -1,24 +1,72 @@
 #![allow(clippy::type_complexity)]
 
+use batch::NodeCommands;
 use bevy_app::prelude::*;
 use bevy_ecs::{
-    component::HookContext, prelude::*, schedule::ScheduleLabel, system::SystemId,
+    component::{HookContext, Mutable},
+    prelude::*,
+    query::ROQueryItem,
+    schedule::ScheduleLabel,
+    system::SystemId,
     world::DeferredWorld,
 };
-use bevy_math::{Curve, curve::EaseFunction};
-use dynamic_systems::DynamicSystems;
-use lens::{AnimationLens, FieldGetter};
+use bevy_log::{error, warn};
+use bevy_math::{
+    Curve,
+    curve::{EaseFunction, FunctionCurve, Interval as CurveInterval},
+};
+use bevy_math::{Quat, Vec2, Vec3};
+use bevy_platform::collections::HashSet;
+use bevy_reflect::{GetTypeRegistration, Reflect, std_traits::ReflectDefault};
+use blend::{BlendBuffer, BufferedWrite, FieldWriter};
+use dynamic_systems::{DynamicObservers, DynamicSystems};
+use lens::{AnimationLens, FieldGetter, register_node_components};
 use playhead::PlayheadMove;
-use std::time::Duration;
+use spline::{SplineKeyframes, SplineNeighbors};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    sync::Arc,
+    time::{Duration, TryFromFloatSecsError},
+};
 
+mod animate;
+mod batch;
+mod blend;
+mod builder;
+mod crossfade;
+#[cfg(feature = "serialize")]
+mod data;
+mod diagnostics;
 pub mod drivers;
 mod dynamic_systems;
 mod lens;
 mod lerp;
+mod modifier;
 pub mod playhead;
+mod snap;
+mod spline;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-pub use lens::{DynamicFieldLens, FieldLens};
-pub use lerp::AnimationLerp;
+pub use animate::AnimateCommands;
+#[cfg(feature = "derive")]
+pub use bevy_keyframe_derive::AnimationLerp;
+pub use blend::{BlendMode, Blending, FieldId};
+pub use builder::AnimationBuilder;
+pub use crossfade::{CrossFade, CrossFadeWeight};
+#[cfg(feature = "serialize")]
+pub use data::{AnimationData, AnimationValue, LeafData, LensRegistry};
+pub use diagnostics::{KeyframeDiagnostics, KeyframeDiagnosticsPlugin};
+pub use drivers::{ParamDriver, SampleRunner};
+pub use lens::{DynamicFieldLens, FieldAccess, FieldLens, OptionFieldLens, ReflectFieldLens};
+#[cfg(feature = "firewheel")]
+pub use lerp::Pan;
+pub use lerp::{AdditiveLerp, AnimationLerp, Seconds};
+pub use modifier::Modifier;
+pub use playhead::{AnimationTargetIndex, AnimationsTargeting, step_animation};
+pub use snap::Snap;
+pub use spline::Spline;
 
 #[derive(Debug)]
 pub struct KeyframePlugin;
@@ -36,12 +84,30 @@ pub struct Animate;
 impl Plugin for KeyframePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<dynamic_systems::DynamicSystemRegistry>()
+            .init_resource::<Blending>()
+            .init_resource::<drivers::GlobalPlaybackState>()
             .init_resource::<playhead::PlayheadSteps>()
+            .init_resource::<PendingCompletions>()
+            .init_resource::<playhead::AnimationTargetIndex>()
             .init_resource::<dynamic_systems::DynamicObserverRegistry>()
+            .register_type::<Animation>()
+            .register_type::<AnimationOf>()
+            .register_type::<Animations>()
+            .register_type::<AnimationTarget>()
+            .register_type::<AnimationComplete>()
+            .register_type::<AnimationCurve>()
+            .register_type::<AnimationDuration>()
+            .register_type::<AnimationSpeed>()
+            .register_type::<Delay>()
+            .register_type::<StartOffset>()
+            .register_type::<DeltaBaseline>()
+            .register_type::<ObserverDriven>()
+            .register_type::<drivers::TimeDriver>()
             .init_schedule(Animate)
             .configure_sets(
                 PreUpdate,
                 (
+                    AnimationSystems::Driver.run_if(drivers::GlobalPlaybackState::is_playing),
                     AnimationSystems::Playhead.after(AnimationSystems::Driver),
                     AnimationSystems::Animate.after(AnimationSystems::Playhead),
                 ),
@@ -52,26 +118,93 @@ impl Plugin for KeyframePlugin {
                     (default_animation_target, propagate_animation_target)
                         .chain()
                         .before(AnimationSystems::Driver),
-                    drivers::TimeDriver::drive_playhead.in_set(AnimationSystems::Driver),
+                    playhead::SequenceLayout::update.before(AnimationSystems::Driver),
+                    Spline::update_neighbors.before(AnimationSystems::Driver),
+                    (
+                        drivers::TimeDriver::drive_playhead,
+                        drivers::SampleRunner::drive_playhead,
+                        drivers::ParamDriver::drive_playhead,
+                    )
+                        .in_set(AnimationSystems::Driver),
                     playhead::AnimationPlayhead::handle_movement.in_set(AnimationSystems::Playhead),
+                    playhead::AnimationProgress::update.in_set(AnimationSystems::Playhead),
+                    playhead::AnimationTargetIndex::update.in_set(AnimationSystems::Playhead),
                     playhead::AnimationPlayhead::apply_movement.in_set(AnimationSystems::Animate),
                 ),
             )
+            // Fixed drivers are evaluated as soon as they move. Playheads that
+            // didn't move since the last pass have nothing left to sweep, so
+            // neither pass applies the other's movements again.
+            .add_systems(
+                FixedPreUpdate,
+                (
+                    drivers::TimeDriver::drive_playhead_fixed
+                        .run_if(drivers::GlobalPlaybackState::is_playing),
+                    playhead::AnimationPlayhead::handle_movement,
+                    playhead::AnimationPlayhead::apply_movement,
+                )
+                    .chain(),
+            )
             .add_systems(
                 Last,
                 dynamic_systems::handle_insertions
                     .run_if(resource_changed::<dynamic_systems::DynamicSystemRegistry>),
             )
-            .add_systems(Animate, AnimationCallback::handle_movement)
-            .add_observer(drivers::TimeDriver::observe_sequence);
+            .add_systems(
+                Animate,
+                (
+                    AnimationCallback::handle_movement,
+                    AnimationSystem::handle_movement,
+                    CrossFade::update_weight,
+                    Snap::handle_movement,
+                ),
+            )
+            .add_observer(drivers::TimeDriver::observe_sequence)
+            .add_observer(AnimationComplete::observe_sequence);
+
+        // Generic nodes can only be registered for concrete
+        // types, so we cover the most common fields.
+        register_nodes::<f32>(app);
+        register_nodes::<Vec2>(app);
+        register_nodes::<Vec3>(app);
+        register_nodes::<Quat>(app);
+
+        #[cfg(feature = "serialize")]
+        app.init_resource::<LensRegistry>();
     }
 }
 
-#[derive(Debug, Component)]
+/// Register the reflected node components for values of type `T`.
+///
+/// Call this for any other field types that should be saved in
+/// scenes or inspected.
+pub fn register_nodes<T>(app: &mut App)
+where
+    T: AnimationLerp,
+    Keyframe<T>: GetTypeRegistration,
+    Delta<T>: GetTypeRegistration,
+    Shift<T>: GetTypeRegistration,
+    Interval<T>: GetTypeRegistration,
+{
+    app.register_type::<Keyframe<T>>()
+        .register_type::<Delta<T>>()
+        .register_type::<Shift<T>>()
+        .register_type::<Interval<T>>();
+}
+
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Eq)]
+#[reflect(Component, FromWorld, Clone, PartialEq)]
 #[relationship(relationship_target = Animations)]
-pub struct AnimationOf(pub Entity);
+pub struct AnimationOf(#[entities] pub Entity);
+
+impl FromWorld for AnimationOf {
+    fn from_world(_world: &mut World) -> Self {
+        Self(Entity::PLACEHOLDER)
+    }
+}
 
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Reflect, Default)]
+#[reflect(Component, FromWorld, Default)]
 #[relationship_target(relationship = AnimationOf, linked_spawn)]
 #[require(playhead::AnimationPlayhead, Animation)]
 pub struct Animations(Vec<Entity>);
@@ -86,61 +219,559 @@ macro_rules! animations {
     };
 }
 
-#[derive(Component, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[reflect(Component, Default, Clone, PartialEq, Hash)]
 pub enum Animation {
+    /// Children play one after another, each starting when the previous one ends.
     #[default]
     Sequence,
+    /// Children all start with this node and each runs for its own duration.
+    ///
+    /// Every child maps the playhead into its own time independently, so a
+    /// shorter child finishes at its own end while longer ones continue. The
+    /// node lasts as long as its longest child.
     Parallel,
+    /// A terminal node, timed by its own [`AnimationDuration`].
+    ///
+    /// Any [`Animations`] attached to a leaf are ignored when
+    /// laying out the sequence, and a warning is logged.
     Leaf,
 }
 
-#[derive(Component, Default, PartialEq, Eq)]
+/// What to do with an animation hierarchy once its sequence completes.
+///
+/// This should be placed on the entity that holds the playhead.
+/// Repeating sequences aren't complete until their driver stops.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Clone, PartialEq, Hash)]
 pub enum AnimationComplete {
+    /// Leave the hierarchy in place.
     #[default]
     Preserve,
+    /// Remove the animation nodes, leaving the animation target intact.
     Remove,
+    /// Despawn the entity along with its animation nodes.
     Despawn,
 }
 
-#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Sequences that completed during the current stage of movements.
+///
+/// Completion is deferred until the stage has been animated, so the
+/// final values, callbacks, and events land before anything is removed.
+#[derive(Resource, Default)]
+pub(crate) struct PendingCompletions(Vec<Entity>);
+
+impl AnimationComplete {
+    fn observe_sequence(
+        trigger: Trigger<playhead::SequenceEvent>,
+        mut pending: ResMut<PendingCompletions>,
+    ) {
+        if !matches!(*trigger, playhead::SequenceEvent::SequenceCompleted) {
+            return;
+        }
+
+        pending.0.push(trigger.target());
+    }
+
+    /// Complete every sequence that finished during the last stage.
+    ///
+    /// This runs after the stage's [`Animate`] pass, by which point the
+    /// driver has also decided whether to repeat.
+    pub(crate) fn apply_pending(world: &mut World) {
+        let pending = core::mem::take(&mut world.resource_mut::<PendingCompletions>().0);
+        for entity in pending {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                Self::complete(entity);
+            }
+        }
+    }
+
+    fn complete(mut entity: EntityWorldMut) {
+        if entity
+            .get::<drivers::TimeDriver>()
+            .is_some_and(|driver| driver.state == drivers::PlaybackState::Play)
+        {
+            return;
+        }
+
+        // Parameters have no inherent direction, so reaching
+        // either end isn't a completion.
+        if entity.contains::<drivers::ParamDriver>() {
+            return;
+        }
+
+        // The follow-up starts first, since completing may despawn this entity.
+        if let Some(next) = entity.get::<WhenComplete>().map(|w| w.0) {
+            entity.world_scope(|world| WhenComplete::start(world, next));
+        }
+
+        Self::apply(entity);
+    }
+
+    /// Apply the policy on `entity`, if it has one.
+    pub(crate) fn apply(mut entity: EntityWorldMut) {
+        let Some(complete) = entity.get::<Self>() else {
+            return;
+        };
+
+        match complete {
+            Self::Preserve => {}
+            Self::Despawn => entity.despawn(),
+            Self::Remove => {
+                let id = entity.id();
+                let is_target = entity
+                    .get::<AnimationTarget>()
+                    .is_none_or(|target| target.0 == id);
+
+                if !is_target {
+                    entity.despawn();
+                    return;
+                }
+
+                // Generic clocks can only be removed by the clock itself.
+                if let Some(remove) = entity.get::<drivers::CustomClock>().and_then(|c| c.0) {
+                    remove(&mut entity);
+                }
+
+                entity.despawn_related::<Animations>().remove::<(
+                    (
+                        Animations,
+                        Animation,
+                        AnimationComplete,
+                        AnimationDuration,
+                        AnimationSpeed,
+                        AnimationTarget,
+                        InheritedTarget,
+                        WhenComplete,
+                        PlayheadMove,
+                        Spline,
+                        StartOffset,
+                        ObserverDriven,
+                    ),
+                    (
+                        playhead::AnimationPlayhead,
+                        playhead::AnimationProgress,
+                        playhead::SequenceDuration,
+                        playhead::SequenceLayout,
+                        playhead::PlayheadDriver,
+                        playhead::ProgressMarkers,
+                    ),
+                    (
+                        drivers::TimeDriver,
+                        drivers::CustomClock,
+                        drivers::FixedTimeDriver,
+                        drivers::SpeedRamp,
+                        drivers::SampleRunner,
+                        drivers::SamplePosition,
+                        drivers::ParamDriver,
+                    ),
+                )>();
+            }
+        }
+    }
+}
+
+/// Start another animation once this sequence completes.
+///
+/// When the sequence on this entity completes, the [`TimeDriver`](drivers::TimeDriver)
+/// on `0` is played, so multi-stage animations can be spawned up front
+/// with [`TimeDriver::paused`](drivers::TimeDriver::paused) and chained together.
+/// Like [`AnimationComplete`], repeating sequences aren't complete until
+/// their driver stops, and the follow-up starts before this entity is
+/// removed or despawned.
+///
+/// ```ignore
+/// let second = commands.spawn((TimeDriver::paused(), animations![...])).id();
+/// commands.spawn((
+///     TimeDriver::default(),
+///     WhenComplete(second),
+///     AnimationComplete::Despawn,
+///     animations![...],
+/// ));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WhenComplete(pub Entity);
+
+impl WhenComplete {
+    fn start(world: &mut World, next: Entity) {
+        match world.get_mut::<drivers::TimeDriver>(next) {
+            Some(mut driver) => driver.play(),
+            None => warn!("animation {next} can't be started, since it has no `TimeDriver`"),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Clone, PartialEq, Hash)]
 pub struct AnimationDuration(pub Duration);
 
 impl AnimationDuration {
+    /// A duration of `seconds` seconds.
+    ///
+    /// Negative, infinite, or NaN durations are treated as zero, with a warning.
+    /// Use [`AnimationDuration::try_secs`] to handle them instead.
     pub fn secs(seconds: f32) -> Self {
-        Self(Duration::from_secs_f32(seconds))
+        Self(secs_or_zero(seconds))
+    }
+
+    /// A duration of `seconds` seconds, or an error if it's negative, infinite, or NaN.
+    pub fn try_secs(seconds: f32) -> Result<Self, TryFromFloatSecsError> {
+        Duration::try_from_secs_f32(seconds).map(Self)
+    }
+
+    pub fn from_millis(millis: u64) -> Self {
+        Self(Duration::from_millis(millis))
+    }
+
+    /// The duration of `frames` frames at `fps` frames per second.
+    pub fn from_frames(frames: u32, fps: f32) -> Self {
+        Self::secs(frames as f32 / fps)
     }
 }
 
-#[derive(Component, Default, PartialEq, Eq)]
-pub struct SampleRunner;
+fn secs_or_zero(seconds: f32) -> Duration {
+    Duration::try_from_secs_f32(seconds).unwrap_or_else(|e| {
+        warn!("invalid animation duration of {seconds} seconds ({e}), using zero");
+        Duration::ZERO
+    })
+}
+
+impl From<Duration> for AnimationDuration {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+/// Scales the rate at which time passes for a node and everything under it.
+///
+/// A speed of `2.0` plays the node's subtree twice as fast, so it takes half
+/// as long in its parent's sequence. Nested speeds multiply. Speeds only
+/// change how fast a node's time passes, so an [`AnimationCurve`] still
+/// covers its whole range over the node.
+///
+/// The speed must be greater than zero. Other speeds are ignored with a warning.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default, Clone, PartialEq)]
+pub struct AnimationSpeed(pub f32);
+
+impl Default for AnimationSpeed {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// A node that does nothing but occupy time in a sequence.
+///
+/// This sets the node's [`AnimationDuration`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Clone, PartialEq, Hash)]
+#[require(AnimationDuration)]
+#[component(on_insert = Self::on_insert_hook)]
+pub struct Delay(pub Duration);
+
+impl Delay {
+    /// A delay of `seconds` seconds, treating invalid durations
+    /// as zero like [`AnimationDuration::secs`].
+    pub fn secs(seconds: f32) -> Self {
+        Self(secs_or_zero(seconds))
+    }
+
+    pub fn from_millis(millis: u64) -> Self {
+        Self(Duration::from_millis(millis))
+    }
+
+    fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
+        let Some(delay) = world.get::<Self>(context.entity).copied() else {
+            return;
+        };
+
+        if let Some(mut duration) = world.get_mut::<AnimationDuration>(context.entity) {
+            duration.0 = delay.0;
+        }
+    }
+}
+
+/// Start a child of an [`Animation::Parallel`] node later than its siblings.
+///
+/// The child's window is shifted by the offset within the group, measured
+/// in the group's own time, and the group lasts until its latest child ends.
+/// This makes staggered entrances easy:
+///
+/// ```ignore
+/// AnimationBuilder::parallel().then_each((0..5).map(|i| {
+///     (
+///         Keyframe(Vec3::ZERO),
+///         AnimationDuration::secs(0.5),
+///         StartOffset::secs(i as f32 * 0.1),
+///     )
+/// }))
+/// ```
+///
+/// Children of other nodes already follow one another, so the offset is
+/// ignored there. Use a [`Delay`] node to leave a gap in a sequence.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Clone, PartialEq, Hash)]
+pub struct StartOffset(pub Duration);
+
+impl StartOffset {
+    /// An offset of `seconds` seconds, treating invalid durations
+    /// as zero like [`AnimationDuration::secs`].
+    pub fn secs(seconds: f32) -> Self {
+        Self(secs_or_zero(seconds))
+    }
+}
 
+/// Trigger an event when the playhead sweeps past the end of this node.
+///
+/// By default, the event is triggered globally. Add [`TargetedEvent`]
+/// to trigger it on the node's [`AnimationTarget`] instead.
 #[derive(Component, Default, PartialEq, Eq)]
-pub struct AnimationEvent<T>(pub T);
+#[require(AnimationDuration)]
+#[component(on_add = Self::on_add_hook)]
+pub struct AnimationEvent<T: Event + Clone>(pub T);
+
+impl<T: Event + Clone> AnimationEvent<T> {
+    fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
+        world
+            .commands()
+            .add_systems_dynamic(Animate, || Self::handle_movement);
+    }
+
+    fn handle_movement(
+        q: Query<
+            (
+                &Self,
+                &AnimationDuration,
+                &PlayheadMove,
+                Option<&AnimationTarget>,
+                Has<TargetedEvent>,
+            ),
+            Changed<PlayheadMove>,
+        >,
+        mut commands: Commands,
+    ) {
+        for (event, duration, movement, target, targeted) in &q {
+            // Only fire when crossing the end moving forward, which for
+            // zero-duration nodes is any forward sweep over them.
+            let (end, _) = crossed_edges(duration, movement);
+            if !end {
+                continue;
+            }
+
+            match target {
+                Some(target) if targeted => commands.trigger_targets(event.0.clone(), target.0),
+                _ => commands.trigger(event.0.clone()),
+            }
+        }
+    }
+}
 
-// TODO: implement shift
-#[derive(Component, Default, Debug)]
+/// Trigger a node's [`AnimationEvent`]s on its [`AnimationTarget`],
+/// so they can be observed per entity.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TargetedEvent;
+
+/// Animate a field from its value when the node becomes active to that value
+/// plus the shift.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
 #[require(AnimationDuration)]
-pub struct Shift<T: AnimationLerp + Clone + Send + Sync + 'static>(pub T);
+#[component(on_add = Self::on_add_hook)]
+pub struct Shift<T: AnimationLerp>(pub T);
 
-#[derive(Component, Debug, Clone, Copy)]
+/// Remaps a node's normalized time before its value is interpolated.
+///
+/// The curve is sampled over `0.0..=1.0`.
+///
+/// Custom curves can't be inspected, so the curve is reflected as an opaque value.
+#[derive(Component, Reflect, Clone)]
+#[reflect(opaque, Component, Default, Clone)]
 #[require(AnimationDuration)]
-pub struct AnimationCurve(pub EaseFunction);
+pub enum AnimationCurve {
+    Ease(EaseFunction),
+    /// Any other curve, like a composite easing or a sampled curve.
+    Custom(Arc<dyn Curve<f32> + Send + Sync>),
+}
+
+impl AnimationCurve {
+    /// Quantize time into `steps` equal jumps, rounding to the nearest level.
+    ///
+    /// A single step holds the start until halfway through the node, then
+    /// jumps to the end. More steps spread the jumps evenly, which suits
+    /// retro or pixelated motion.
+    ///
+    /// Since this remaps time rather than values, it works with any
+    /// [`AnimationLerp`]. Each level is interpolated as usual, so a
+    /// [`Keyframe`] snaps through values evenly spaced along its lerp.
+    pub fn steps(steps: usize) -> Self {
+        let steps = steps.max(1) as f32;
+        Self::custom(FunctionCurve::new(CurveInterval::UNIT, move |t: f32| {
+            (t * steps).round() / steps
+        }))
+    }
+
+    /// Wrap a custom curve.
+    pub fn custom(curve: impl Curve<f32> + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(curve))
+    }
+
+    fn sample(&self, t: f32) -> f32 {
+        match self {
+            Self::Ease(ease) => ease.sample(t).unwrap_or(t),
+            Self::Custom(curve) => curve.sample_clamped(t),
+        }
+    }
+}
 
 impl Default for AnimationCurve {
     fn default() -> Self {
-        AnimationCurve(EaseFunction::Linear)
+        AnimationCurve::Ease(EaseFunction::Linear)
     }
 }
 
-#[derive(Debug, Component, Clone)]
-// #[component(on_insert = Self::on_add_hook)]
+impl From<EaseFunction> for AnimationCurve {
+    fn from(ease: EaseFunction) -> Self {
+        AnimationCurve::Ease(ease)
+    }
+}
+
+impl core::fmt::Debug for AnimationCurve {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Ease(ease) => f.debug_tuple("Ease").field(ease).finish(),
+            Self::Custom(curve) => f.debug_tuple("Custom").field(&curve.domain()).finish(),
+        }
+    }
+}
+
+/// Animate a field between two fixed values.
+///
+/// Unlike [`Keyframe`], both ends are known up front, so the animation
+/// doesn't depend on the field's value when the node becomes active.
+/// An `Interval` takes precedence over a [`Keyframe`] or [`Shift`] on the
+/// same node, which then never capture a starting value.
+#[derive(Debug, Component, Reflect, Clone)]
+#[reflect(Component, Clone)]
+#[require(AnimationDuration)]
+#[component(on_add = Self::on_add_hook)]
 pub struct Interval<T: AnimationLerp> {
     pub start: T,
     pub end: T,
 }
 
-#[derive(Component, Debug)]
-pub struct AnimationTarget(pub Entity);
+impl<T: AnimationLerp> Interval<T> {
+    fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
+        let mut commands = world.commands();
+        commands.queue(register_node_components::<T>);
+        commands.init_resource::<BlendBuffer<T>>();
+        commands.add_systems_dynamic(Animate, || Self::handle_movement);
+    }
+
+    fn handle_movement(
+        interval: Query<
+            (
+                Entity,
+                &Self,
+                &AnimationDuration,
+                Option<&AnimationLens<T>>,
+                &AnimationTarget,
+                &PlayheadMove,
+                Option<&AnimationCurve>,
+            ),
+            Changed<PlayheadMove>,
+        >,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+    ) -> Result {
+        for (entity, interval, duration, lens_ref, target_ref, movement, curve) in &interval {
+            if movement.start == movement.end && !duration.0.is_zero() {
+                continue;
+            }
+
+            let lens = AnimationLens::resolve(lens_ref, &lens, entity)?;
+            let target = FieldAccess::new(target.get_mut(target_ref.0)?);
+
+            let t = get_time(duration.0, movement.end, curve);
+            let value = interval.start.animation_lerp(&interval.end, t);
+            writer.write(lens, entity, target_ref.0, target, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The interval a [`Keyframe`] or [`Shift`] captured when it became active.
+#[derive(Debug, Component, Clone)]
+pub(crate) struct CapturedInterval<T: AnimationLerp>(Interval<T>);
+
+impl<T: AnimationLerp> CapturedInterval<T> {
+    /// Get a node's captured interval, capturing it from the target first
+    /// if the node hasn't captured one yet.
+    ///
+    /// If we're moving forward from the very beginning, the target may have
+    /// changed since we last captured it, so we re-capture. `end` computes
+    /// the end of a fresh interval from its start.
+    fn get_or_capture<'a>(
+        captured: Option<&'a Self>,
+        entity: Entity,
+        movement: &PlayheadMove,
+        lens: &DynamicFieldLens<T>,
+        target: FieldAccess<T>,
+        end: impl FnOnce(&T) -> T,
+        commands: &impl NodeCommands,
+    ) -> Result<Cow<'a, Interval<T>>> {
+        match (movement.enters_from_start(), captured) {
+            // The captured interval is borrowed so large values aren't cloned every frame.
+            (false, Some(captured)) => Ok(Cow::Borrowed(&captured.0)),
+            (true, _) | (false, None) => {
+                let start = lens.get_field(target)?;
+                let end = end(&start);
+                let interval = Interval { start, end };
+
+                let captured = Self(interval.clone());
+                commands.command_scope(|mut commands| {
+                    commands.entity(entity).insert(captured);
+                });
+
+                Ok(Cow::Owned(interval))
+            }
+        }
+    }
+}
+
+/// The entity an animation node writes to.
+///
+/// Roots without a target animate themselves. Every node inherits its
+/// parent's target unless it has its own, so a controller entity can hold
+/// the animation while the fields live elsewhere, like a child mesh.
+///
+/// ```ignore
+/// commands.spawn((
+///     AnimationTarget(mesh),
+///     lens!(Transform::translation),
+///     animations![(Keyframe(Vec3::X), AnimationDuration::secs(1.0))],
+/// ));
+/// ```
+///
+/// A target inserted on a nested node overrides the inherited one for
+/// that node and its descendants.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, FromWorld, Clone, PartialEq)]
+pub struct AnimationTarget(#[entities] pub Entity);
+
+impl FromWorld for AnimationTarget {
+    fn from_world(_world: &mut World) -> Self {
+        Self(Entity::PLACEHOLDER)
+    }
+}
+
+/// Marks an [`AnimationTarget`] that was inherited from a parent node.
+///
+/// A node whose target differs from this was given one explicitly, so
+/// propagation leaves it and its descendants alone.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct InheritedTarget(Entity);
 
 fn default_animation_target(
     new_roots: Query<
@@ -158,264 +789,1615 @@ fn default_animation_target(
     }
 }
 
+/// Log that an animation hierarchy loops back on itself at `node`.
+///
+/// Relationships can be inserted by hand, so nothing stops a node from
+/// becoming its own ancestor. Walks over the hierarchy bail out when
+/// they find one rather than looping forever.
+pub(crate) fn report_cycle(node: Entity) {
+    error!("animation node {node} is its own ancestor; skipping the cycle");
+}
+
 fn propagate_animation_target(
-    lenses: Query<Entity, Added<AnimationTarget>>,
+    lenses: Query<(Entity, &AnimationTarget, Option<&InheritedTarget>), Changed<AnimationTarget>>,
     hierarchy: Query<&Animations>,
-    conflicts: Query<Has<AnimationTarget>>,
+    conflicts: Query<(Option<&AnimationTarget>, Option<&InheritedTarget>)>,
     mut commands: Commands,
 ) -> Result {
-    for new_target in &lenses {
-        fn recurse(
-            new_target: Entity,
-            node: Entity,
-            hierarchy: &Query<&Animations>,
-            conflicts: &Query<Has<AnimationTarget>>,
-            mut commands: Commands,
-        ) -> Result {
+    for (source, target, inherited) in &lenses {
+        // Inherited targets were already pushed down by their source.
+        if inherited.is_some_and(|inherited| inherited.0 == target.0) {
+            continue;
+        }
+
+        let target = target.0;
+
+        // An explicit stack keeps deep hierarchies from overflowing.
+        let mut stack = vec![source];
+        let mut visited = HashSet::from([source]);
+        while let Some(node) = stack.pop() {
             for child in hierarchy.get(node).ok().iter().flat_map(|a| a.iter()) {
-                if !conflicts.get(child)? {
-                    commands.entity(child).insert(AnimationTarget(new_target));
-                    recurse(new_target, child, hierarchy, conflicts, commands.reborrow())?;
+                if !visited.insert(child) {
+                    report_cycle(child);
+                    continue;
                 }
-            }
 
-            Ok(())
-        }
+                let (existing, inherited) = conflicts.get(child)?;
 
-        recurse(
-            new_target,
-            new_target,
-            &hierarchy,
-            &conflicts,
-            commands.reborrow(),
-        )?;
+                // Explicit overrides stop propagation for their whole subtree.
+                let explicit = existing.is_some_and(|existing| {
+                    inherited.is_none_or(|inherited| inherited.0 != existing.0)
+                });
+                if explicit {
+                    continue;
+                }
+
+                if existing.is_none_or(|existing| existing.0 != target) {
+                    commands
+                        .entity(child)
+                        .insert((AnimationTarget(target), InheritedTarget(target)));
+                }
+                stack.push(child);
+            }
+        }
     }
 
     Ok(())
 }
 
-#[derive(Component, Default, Debug)]
+/// Animate a field from its current value to this one.
+///
+/// The starting value is read from the target when the playhead first
+/// moves into the node, and re-read only when the playhead moves forward
+/// from the node's very start. Reversing partway through and playing
+/// forward again keeps the original start, even if something else changed
+/// the field in the meantime, so scrubbing back and forth always retraces
+/// the same interval. Reversing all the way to the start and playing
+/// forward again picks up the field's value at that point.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
 #[require(AnimationDuration)]
 #[component(on_add = Self::on_add_hook)]
 pub struct Keyframe<T: AnimationLerp>(pub T);
 
+/// Evaluate this node with observers rather than the polling systems.
+///
+/// Polling checks every animation node for movement each stage, while
+/// observers run only for the node that moved. `cargo bench --bench animation`
+/// compares the two for a few playing nodes among many idle ones.
+///
+/// Only [`Keyframe`], [`Shift`] and [`Delta`] respond to observer-driven movement.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, Default, Clone, PartialEq)]
+pub struct ObserverDriven;
+
 fn get_time(duration: Duration, instant: f32, curve: Option<&AnimationCurve>) -> f32 {
     let duration = duration.as_secs_f32();
+    // Floating point error or a seek can place the instant slightly outside
+    // the node, so we clamp rather than extrapolate past either end.
     let t = if duration == 0.0 {
         1.0
     } else {
-        instant / duration
+        (instant / duration).clamp(0.0, 1.0)
     };
 
     match curve {
-        Some(curve) => curve.0.sample(t).unwrap_or(t),
+        Some(curve) => curve.sample(t),
         None => t,
     }
 }
 
-// TODO: manage fetching
+/// The components of a [`Keyframe`] node.
+type KeyframeNode<T> = (
+    Entity,
+    &'static Keyframe<T>,
+    &'static AnimationDuration,
+    Option<&'static AnimationLens<T>>,
+    &'static AnimationTarget,
+    Option<&'static CapturedInterval<T>>,
+    Option<&'static AnimationCurve>,
+    Option<&'static SplineNeighbors>,
+);
+
 impl<T: AnimationLerp> Keyframe<T> {
     fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
-        // world
-        //     .commands()
-        //     .add_observer_dynamic(Self::observe_movement);
+        let mut commands = world.commands();
+        commands.queue(register_node_components::<T>);
+        commands.init_resource::<BlendBuffer<T>>();
+        commands.add_systems_dynamic(Animate, || Self::handle_movement);
+        commands.add_observer_dynamic(Self::observe_movement);
     }
 
     fn handle_movement(
-        delta: Query<(
-            &Self,
-            &AnimationDuration,
-            &AnimationLens<T>,
-            &AnimationTarget,
-            Option<&Interval<T>>,
-            Option<&AnimationCurve>,
-        )>,
+        keyframe: Query<
+            (KeyframeNode<T>, &PlayheadMove),
+            (Changed<PlayheadMove>, Without<Interval<T>>),
+        >,
         lens: Query<&DynamicFieldLens<T>>,
-        target: Query<EntityMut>,
-        mut commands: Commands,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        splines: SplineKeyframes<T>,
+        commands: ParallelCommands,
+    ) -> Result {
+        let nodes = keyframe
+            .iter()
+            .map(|(node, movement)| (node.4.0, (node, movement)));
+
+        batch::apply_by_target(
+            nodes,
+            &mut target,
+            &mut writer,
+            |&(node, movement), target, writer| {
+                Self::apply(node, movement, &lens, target, writer, &splines, &commands)
+            },
+        )
+    }
+
+    fn observe_movement(
+        trigger: Trigger<PlayheadMove>,
+        keyframe: Query<KeyframeNode<T>, Without<Interval<T>>>,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        splines: SplineKeyframes<T>,
+        commands: Commands,
     ) -> Result {
-        todo!();
+        let Ok(node) = keyframe.get(trigger.target()) else {
+            return Ok(());
+        };
+
+        let target = FieldAccess::new(target.get_mut(node.4.0)?);
+        let commands = RefCell::new(commands);
+        let write = Self::apply(
+            node,
+            trigger.event(),
+            &lens,
+            target,
+            &writer,
+            &splines,
+            &commands,
+        )?;
+
+        if let Some(write) = write {
+            writer.buffer(write);
+        }
 
         Ok(())
     }
 
-    // fn observe_movement(
-    //     trigger: Trigger<playhead::PlayheadMove>,
-    //     mut set: ParamSet<(
-    //         Query<(
-    //             &Self,
-    //             &AnimationDuration,
-    //             &AnimationLens<T>,
-    //             &AnimationTarget,
-    //             Option<&Interval<T>>,
-    //             Option<&AnimationCurve>,
-    //         )>,
-    //         Query<&DynamicFieldLens<T>>,
-    //         Query<EntityMut>,
-    //     )>,
-    //     mut commands: Commands,
-    // ) -> Result {
-    //     let entity = trigger.target();
-    //     let delta = set.p0();
-    //     let Ok((keyframe, duration, lens_ref, target, interval, curve)) = delta.get(entity) else {
-    //         return Ok(());
-    //     };
-    //
-    //     // copy all the things
-    //     let (keyframe, duration, lens_ref, target_entity, interval, curve) = (
-    //         keyframe.0.clone(),
-    //         duration.0,
-    //         lens_ref.get(),
-    //         target.0,
-    //         interval.cloned(),
-    //         curve.copied(),
-    //     );
-    //     let lens = set.p1().get(lens_ref)?.clone();
-    //     let mut target = set.p2();
-    //     let mut target = target.get_mut(target_entity)?;
-    //
-    //     // if we're moving forward and start at zero,
-    //     // add the interval!
-    //
-    //     let just_started = trigger.start == 0.0 && trigger.end > 0.0;
-    //
-    //     let interval = match (just_started, interval) {
-    //         (true, _) | (false, None) => {
-    //             let start = lens.get_field(target.reborrow())?;
-    //             let interval = Interval {
-    //                 start,
-    //                 end: keyframe,
-    //             };
-    //
-    //             commands.entity(trigger.target()).insert(interval.clone());
-    //
-    //             interval
-    //         }
-    //
-    //         (_, Some(interval)) => interval,
-    //     };
-    //
-    //     let duration = duration.as_secs_f32();
-    //     let t = if duration == 0.0 {
-    //         1.0
-    //     } else {
-    //         trigger.end / duration
-    //     };
-    //
-    //     let t = match curve {
-    //         Some(curve) => curve.0.sample(t).unwrap_or(t),
-    //         None => t,
-    //     };
-    //
-    //     let new_value = interval.start.animation_lerp(&interval.end, t);
-    //     lens.set_field(target, new_value)?;
-    //
-    //     Ok(())
-    // }
-}
-
-#[derive(Component, Default, Debug)]
+    // Unlike `Delta`, keyframes need to know where they're coming from, so we
+    // lazily capture the starting value from the target the first time the
+    // playhead enters the node.
+    fn apply(
+        (entity, keyframe, duration, lens_ref, target_ref, interval, curve, neighbors): ROQueryItem<
+            KeyframeNode<T>,
+        >,
+        movement: &PlayheadMove,
+        lens: &Query<&DynamicFieldLens<T>>,
+        mut target: FieldAccess<T>,
+        writer: &FieldWriter<T>,
+        splines: &SplineKeyframes<T>,
+        commands: &impl NodeCommands,
+    ) -> Result<Option<BufferedWrite<T>>> {
+        // Zero-duration keyframes snap to their value, so
+        // they apply even though the movement is empty.
+        if movement.start == movement.end && !duration.0.is_zero() {
+            return Ok(None);
+        }
+
+        let lens = AnimationLens::resolve(lens_ref, lens, entity)?;
+        let interval = CapturedInterval::get_or_capture(
+            interval,
+            entity,
+            movement,
+            lens,
+            target.reborrow(),
+            |_| keyframe.0.clone(),
+            commands,
+        )?;
+        let start = &interval.start;
+
+        let t = get_time(duration.0, movement.end, curve);
+        match neighbors {
+            Some(neighbors) => {
+                let value = splines.sample(neighbors, start, &keyframe.0, duration, t);
+                writer.try_write(lens, entity, target_ref.0, target, value)
+            }
+            None => writer.try_modify(lens, entity, target_ref.0, target, |field| {
+                start.animation_lerp_into(&keyframe.0, t, field);
+            }),
+        }
+    }
+}
+
+/// Animate several fields of the same type from one node.
+///
+/// A node can only hold one [`Keyframe<T>`], so this pairs each
+/// keyframe with its own lens instead. Each entry otherwise
+/// behaves like a separate [`Keyframe`].
+#[derive(Component, Debug)]
 #[require(AnimationDuration)]
 #[component(on_add = Self::on_add_hook)]
-pub struct Delta<T: AnimationLerp>(pub T);
+pub struct Keyframes<T: AnimationLerp> {
+    keyframes: Vec<(DynamicFieldLens<T>, T)>,
+    starts: Vec<T>,
+}
+
+impl<T: AnimationLerp> Default for Keyframes<T> {
+    fn default() -> Self {
+        Self {
+            keyframes: Vec::new(),
+            starts: Vec::new(),
+        }
+    }
+}
+
+impl<T: AnimationLerp> Keyframes<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a keyframe for the field targeted by `lens`.
+    pub fn with(mut self, lens: impl Into<DynamicFieldLens<T>>, value: T) -> Self {
+        self.keyframes.push((lens.into(), value));
+        self
+    }
 
-impl<T: AnimationLerp> Delta<T> {
     fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
-        // dynamically register the necessary systems for convenience
-        world
-            .commands()
-            .add_systems_dynamic(Animate, || Self::handle_movement);
+        let mut commands = world.commands();
+        DynamicFieldLens::<T>::register_systems(&mut commands);
+        commands.add_systems_dynamic(Animate, || Self::handle_movement);
     }
 
-    // This is quite beautiful because it can be stateless. No fetching required.
     fn handle_movement(
-        delta: Query<
+        mut keyframes: Query<
             (
-                &Self,
+                Entity,
+                &mut Self,
                 &AnimationDuration,
-                &AnimationLens<T>,
                 &AnimationTarget,
                 &PlayheadMove,
                 Option<&AnimationCurve>,
             ),
-            // This is the key bit. Any time this changes, we can evaluate an animation.
             Changed<PlayheadMove>,
         >,
-        lens: Query<&DynamicFieldLens<T>>,
         mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
     ) -> Result {
-        for (delta, duration, lens_ref, target_ref, movement, curve) in &delta {
-            let lens = lens.get(lens_ref.get())?;
-            let mut target = target.get_mut(target_ref.0)?;
-
-            // TODO: is this a reasonable skip condition?
-            if movement.start == movement.end {
+        for (entity, mut keyframes, duration, target_ref, movement, curve) in &mut keyframes {
+            if movement.start == movement.end && !duration.0.is_zero() {
                 continue;
             }
 
-            let default_value = T::default();
-
-            let start_time = get_time(duration.0, movement.start, curve);
-            let start = default_value.animation_lerp(&delta.0, start_time);
+            let mut target = FieldAccess::new(target.get_mut(target_ref.0)?);
+            let keyframes = &mut *keyframes;
 
-            let end_time = get_time(duration.0, movement.end, curve);
-            let end = default_value.animation_lerp(&delta.0, end_time);
-
-            let difference = end.difference(&start);
+            // Capture the starting values just like `Keyframe`.
+            let just_started = movement.enters_from_start();
+            if just_started || keyframes.starts.len() != keyframes.keyframes.len() {
+                keyframes.starts = keyframes
+                    .keyframes
+                    .iter()
+                    .map(|(lens, _)| lens.get_field(target.reborrow()))
+                    .collect::<Result<_>>()?;
+            }
 
-            let mut value = lens.get_field(target.reborrow())?;
-            value.accumulate(&difference);
-            lens.set_field(target, value)?;
+            let t = get_time(duration.0, movement.end, curve);
+            for ((lens, end), start) in keyframes.keyframes.iter().zip(&keyframes.starts) {
+                let value = start.animation_lerp(end, t);
+                writer.write(lens, entity, target_ref.0, target.reborrow(), value)?;
+            }
         }
 
         Ok(())
     }
 }
 
-#[derive(Component)]
-#[require(AnimationDuration)]
-#[component(on_insert = Self::on_insert_hook)]
-pub struct AnimationCallback {
-    unregistered_system: Option<Box<dyn FnOnce(&mut World) -> SystemId + Send + Sync>>,
-    system_id: Option<SystemId>,
-}
+/// The components of a [`Shift`] node.
+type ShiftNode<T> = (
+    Entity,
+    &'static Shift<T>,
+    &'static AnimationDuration,
+    Option<&'static AnimationLens<T>>,
+    &'static AnimationTarget,
+    Option<&'static CapturedInterval<T>>,
+    Option<&'static AnimationCurve>,
+);
 
-impl AnimationCallback {
-    pub fn new<S, M>(system: S) -> Self
-    where
-        S: IntoSystem<(), (), M> + Send + Sync + 'static,
-    {
-        Self {
-            unregistered_system: Some(Box::new(move |world| world.register_system(system))),
-            system_id: None,
-        }
+impl<T: AnimationLerp> Shift<T> {
+    fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
+        let mut commands = world.commands();
+        commands.queue(register_node_components::<T>);
+        commands.init_resource::<BlendBuffer<T>>();
+        commands.add_systems_dynamic(Animate, || Self::handle_movement);
+        commands.add_observer_dynamic(Self::observe_movement);
     }
 
-    fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
-        let mut commands = world.commands();
-        commands.queue(move |world: &mut World| {
-            let Some(system) = world
+    fn handle_movement(
+        shift: Query<(ShiftNode<T>, &PlayheadMove), (Changed<PlayheadMove>, Without<Interval<T>>)>,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        commands: ParallelCommands,
+    ) -> Result {
+        let nodes = shift
+            .iter()
+            .map(|(node, movement)| (node.4.0, (node, movement)));
+
+        batch::apply_by_target(
+            nodes,
+            &mut target,
+            &mut writer,
+            |&(node, movement), target, writer| {
+                Self::apply(node, movement, &lens, target, writer, &commands)
+            },
+        )
+    }
+
+    fn observe_movement(
+        trigger: Trigger<PlayheadMove>,
+        shift: Query<ShiftNode<T>, Without<Interval<T>>>,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        commands: Commands,
+    ) -> Result {
+        let Ok(node) = shift.get(trigger.target()) else {
+            return Ok(());
+        };
+
+        let target = FieldAccess::new(target.get_mut(node.4.0)?);
+        let commands = RefCell::new(commands);
+        if let Some(write) = Self::apply(node, trigger.event(), &lens, target, &writer, &commands)?
+        {
+            writer.buffer(write);
+        }
+
+        Ok(())
+    }
+
+    // Like `Keyframe`, we capture the interval when the node becomes active so
+    // external changes to the field mid-play don't affect the animation.
+    fn apply(
+        (entity, shift, duration, lens_ref, target_ref, interval, curve): ROQueryItem<ShiftNode<T>>,
+        movement: &PlayheadMove,
+        lens: &Query<&DynamicFieldLens<T>>,
+        mut target: FieldAccess<T>,
+        writer: &FieldWriter<T>,
+        commands: &impl NodeCommands,
+    ) -> Result<Option<BufferedWrite<T>>> {
+        if movement.start == movement.end && !duration.0.is_zero() {
+            return Ok(None);
+        }
+
+        let lens = AnimationLens::resolve(lens_ref, lens, entity)?;
+        let interval = CapturedInterval::get_or_capture(
+            interval,
+            entity,
+            movement,
+            lens,
+            target.reborrow(),
+            |start| {
+                let mut end = start.clone();
+                end.accumulate(&shift.0);
+                end
+            },
+            commands,
+        )?;
+
+        let t = get_time(duration.0, movement.end, curve);
+        writer.try_modify(lens, entity, target_ref.0, target, |field| {
+            interval.start.animation_lerp_into(&interval.end, t, field);
+        })
+    }
+}
+
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+#[require(AnimationDuration)]
+#[component(on_add = Self::on_add_hook)]
+pub struct Delta<T: AnimationLerp>(pub T);
+
+/// Anchor a [`Delta`] node to the value its field had when its sequence started.
+///
+/// A plain delta adds the change since the last frame, so repeating it for
+/// a long time slowly accumulates floating point error. With this, the node
+/// records its field's value the first time it plays after each
+/// [`SequenceStarted`](playhead::SequenceEvent::SequenceStarted), like at
+/// the start of each loop, and writes that value plus its progress instead.
+/// Ping-pong playback then returns to where it started, loop after loop.
+///
+/// Each time the node re-anchors, it triggers a [`DeltaDrift`] reporting how
+/// far the field strayed from where the previous anchor expected it.
+///
+/// Because the node writes the whole value, it overrides anything else
+/// animating the same field while it plays.
+///
+/// ```ignore
+/// (Delta(Vec3::X * 100.0), DeltaBaseline, AnimationDuration::secs(1.0))
+/// ```
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, Default, Clone, PartialEq)]
+pub struct DeltaBaseline;
+
+/// Triggered on a [`DeltaBaseline`] node when it re-anchors.
+///
+/// `drift` is the difference between the field's value and the value
+/// the previous anchor expected at the same point in the node. Playback
+/// that returns to where it started, like ping-pong, expects no drift,
+/// so anything here was made by something else writing to the field.
+/// Sequences that don't return, like restarting ones, also include the
+/// change they made in the last loop.
+#[derive(Event, Debug, Clone)]
+pub struct DeltaDrift<T: AnimationLerp> {
+    pub drift: T,
+}
+
+/// The value a [`DeltaBaseline`] node's field had when its sequence started.
+#[derive(Debug, Component, Clone)]
+pub(crate) struct CapturedBaseline<T: AnimationLerp> {
+    value: T,
+    /// Whether the sequence has started again since this was captured.
+    stale: bool,
+}
+
+/// The components of a [`Delta`] node.
+type DeltaNode<T> = (
+    Entity,
+    &'static Delta<T>,
+    &'static AnimationDuration,
+    Option<&'static AnimationLens<T>>,
+    &'static AnimationTarget,
+    Option<&'static AnimationCurve>,
+    Has<DeltaBaseline>,
+    Option<&'static CapturedBaseline<T>>,
+);
+
+impl<T: AnimationLerp> Delta<T> {
+    fn on_add_hook(mut world: DeferredWorld, _context: HookContext) {
+        // dynamically register the necessary systems for convenience
+        let mut commands = world.commands();
+        commands.queue(register_node_components::<T>);
+        commands.init_resource::<BlendBuffer<T>>();
+        commands.add_systems_dynamic(Animate, || Self::handle_movement);
+        commands.add_observer_dynamic(Self::observe_movement);
+        commands.add_observer_dynamic(Self::observe_sequence);
+    }
+
+    /// Mark the baselines under a starting sequence to be captured again.
+    ///
+    /// Nodes with their own [`PlayheadDriver`](playhead::PlayheadDriver)
+    /// belong to a different sequence, so they're left alone.
+    fn observe_sequence(
+        trigger: Trigger<playhead::SequenceEvent>,
+        hierarchy: Query<(Option<&Animations>, Has<playhead::PlayheadDriver>)>,
+        mut baselines: Query<&mut CapturedBaseline<T>>,
+    ) {
+        if *trigger != playhead::SequenceEvent::SequenceStarted {
+            return;
+        }
+
+        let mut stack = vec![trigger.target()];
+        let mut visited = HashSet::new();
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                report_cycle(node);
+                continue;
+            }
+
+            if let Ok(mut baseline) = baselines.get_mut(node) {
+                baseline.stale = true;
+            }
+
+            let Ok((Some(children), _)) = hierarchy.get(node) else {
+                continue;
+            };
+            stack.extend(
+                children
+                    .iter()
+                    .filter(|child| !hierarchy.get(*child).is_ok_and(|(_, driven)| driven)),
+            );
+        }
+    }
+
+    fn handle_movement(
+        // This is the key bit. Any time this changes, we can evaluate an animation.
+        delta: Query<(DeltaNode<T>, &PlayheadMove), Changed<PlayheadMove>>,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        commands: ParallelCommands,
+    ) -> Result {
+        let nodes = delta
+            .iter()
+            .map(|(node, movement)| (node.4.0, (node, movement)));
+
+        batch::apply_by_target(
+            nodes,
+            &mut target,
+            &mut writer,
+            |&(node, movement), target, writer| {
+                Self::apply(node, movement, &lens, target, writer, &commands)
+            },
+        )
+    }
+
+    fn observe_movement(
+        trigger: Trigger<PlayheadMove>,
+        delta: Query<DeltaNode<T>>,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        commands: Commands,
+    ) -> Result {
+        let Ok(node) = delta.get(trigger.target()) else {
+            return Ok(());
+        };
+
+        let target = FieldAccess::new(target.get_mut(node.4.0)?);
+        let commands = RefCell::new(commands);
+        if let Some(write) = Self::apply(node, trigger.event(), &lens, target, &writer, &commands)?
+        {
+            writer.buffer(write);
+        }
+
+        Ok(())
+    }
+
+    // This is quite beautiful because it can be stateless, unless anchored.
+    // No fetching required.
+    fn apply(
+        (entity, delta, duration, lens_ref, target_ref, curve, anchored, baseline): ROQueryItem<
+            DeltaNode<T>,
+        >,
+        movement: &PlayheadMove,
+        lens: &Query<&DynamicFieldLens<T>>,
+        mut target: FieldAccess<T>,
+        writer: &FieldWriter<T>,
+        commands: &impl NodeCommands,
+    ) -> Result<Option<BufferedWrite<T>>> {
+        let lens = AnimationLens::resolve(lens_ref, lens, entity)?;
+
+        // TODO: is this a reasonable skip condition?
+        if movement.start == movement.end {
+            return Ok(None);
+        }
+
+        let default_value = T::identity();
+
+        let start_time = get_time(duration.0, movement.start, curve);
+        let start = default_value.animation_lerp(&delta.0, start_time);
+
+        let end_time = get_time(duration.0, movement.end, curve);
+        let end = default_value.animation_lerp(&delta.0, end_time);
+
+        if anchored {
+            // Anchored deltas write their progress from a fixed baseline,
+            // so rounding doesn't build up from frame to frame.
+            let captured;
+            let baseline = match baseline {
+                Some(baseline) if !baseline.stale => &baseline.value,
+                _ => {
+                    let field = lens.get_field(target.reborrow())?;
+                    captured = field.difference(&start);
+
+                    let drift = baseline.map(|previous| {
+                        let mut expected = previous.value.clone();
+                        expected.accumulate(&start);
+                        field.difference(&expected)
+                    });
+
+                    let baseline = CapturedBaseline {
+                        value: captured.clone(),
+                        stale: false,
+                    };
+                    commands.command_scope(|mut commands| {
+                        commands.entity(entity).insert(baseline);
+                        if let Some(drift) = drift {
+                            commands.trigger_targets(DeltaDrift { drift }, entity);
+                        }
+                    });
+
+                    &captured
+                }
+            };
+
+            writer.try_modify(lens, entity, target_ref.0, target, |field| {
+                field.clone_from(baseline);
+                field.accumulate(&end);
+            })
+        } else {
+            let difference = end.difference(&start);
+
+            writer.try_modify(lens, entity, target_ref.0, target, |field| {
+                field.accumulate(&difference);
+            })
+        }
+    }
+}
+
+/// The context in which an [`AnimationCallback`] was run.
+#[derive(Debug, Clone, Copy)]
+pub struct CallbackContext {
+    /// The animation node holding the callback.
+    pub animation: Entity,
+    /// The node's animation target, if any.
+    pub target: Option<Entity>,
+    /// The movement that completed the node.
+    pub movement: PlayheadMove,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CallbackSystem {
+    Plain(SystemId),
+    WithInput(SystemId<In<CallbackContext>>),
+}
+
+/// The edges of a node that run an [`AnimationCallback`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CallbackEdge {
+    /// Run when the playhead reaches the end moving forward.
+    #[default]
+    End,
+    /// Run when the playhead reaches the start moving backward.
+    Start,
+    /// Run on both edges.
+    Both,
+}
+
+/// A one-shot system that's registered the first time its node is inserted.
+struct DeferredSystem<Id> {
+    register: Option<Box<dyn FnOnce(&mut World) -> Id + Send + Sync>>,
+    id: Option<Id>,
+}
+
+impl<Id> DeferredSystem<Id> {
+    fn new(register: impl FnOnce(&mut World) -> Id + Send + Sync + 'static) -> Self {
+        Self {
+            register: Some(Box::new(register)),
+            id: None,
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            register: None,
+            id: None,
+        }
+    }
+}
+
+/// A node component holding a [`DeferredSystem`].
+trait DeferredSystemNode: Component<Mutability = Mutable> + Sized {
+    type Id: Send + Sync + 'static;
+
+    fn deferred_system(&mut self) -> &mut DeferredSystem<Self::Id>;
+
+    /// Register the node's system once the world is available.
+    fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
+        world.commands().queue(move |world: &mut World| {
+            let Some(register) = world
                 .get_mut::<Self>(context.entity)
-                .and_then(|mut cb| cb.unregistered_system.take())
+                .and_then(|mut node| node.deferred_system().register.take())
             else {
                 return;
             };
 
-            let id = system(world);
-            world.get_mut::<Self>(context.entity).unwrap().system_id = Some(id);
+            let id = register(world);
+            if let Some(mut node) = world.get_mut::<Self>(context.entity) {
+                node.deferred_system().id = Some(id);
+            }
         });
     }
+}
+
+/// Whether `movement` reaches the end of a node moving forward and
+/// whether it reaches the start moving backward, in that order.
+///
+/// Requiring the movement to start on the other side of the edge
+/// prevents re-running when the playhead leaves from the boundary.
+/// Zero-duration nodes are a single point, so crossing one forward
+/// reaches its end and crossing it backward reaches its start.
+fn crossed_edges(duration: &AnimationDuration, movement: &PlayheadMove) -> (bool, bool) {
+    let duration = duration.0.as_secs_f32();
+    if duration == 0.0 {
+        return (!movement.reversed, movement.reversed);
+    }
+
+    (
+        movement.start < duration && movement.end >= duration,
+        movement.start > 0.0 && movement.end <= 0.0,
+    )
+}
+
+#[derive(Component)]
+#[require(AnimationDuration)]
+#[component(on_insert = <Self as DeferredSystemNode>::on_insert_hook)]
+pub struct AnimationCallback {
+    system: DeferredSystem<CallbackSystem>,
+    once: Option<Box<dyn FnOnce(&mut World) + Send + Sync>>,
+    edge: CallbackEdge,
+}
+
+impl DeferredSystemNode for AnimationCallback {
+    type Id = CallbackSystem;
+
+    fn deferred_system(&mut self) -> &mut DeferredSystem<CallbackSystem> {
+        &mut self.system
+    }
+}
+
+impl AnimationCallback {
+    pub fn new<S, M>(system: S) -> Self
+    where
+        S: IntoSystem<(), (), M> + Send + Sync + 'static,
+    {
+        Self {
+            system: DeferredSystem::new(move |world| {
+                CallbackSystem::Plain(world.register_system(system))
+            }),
+            once: None,
+            edge: CallbackEdge::End,
+        }
+    }
+
+    /// Create a callback whose system receives the [`CallbackContext`].
+    ///
+    /// This allows a single system to serve many animations.
+    pub fn with_input<S, M>(system: S) -> Self
+    where
+        S: IntoSystem<In<CallbackContext>, (), M> + Send + Sync + 'static,
+    {
+        Self {
+            system: DeferredSystem::new(move |world| {
+                CallbackSystem::WithInput(world.register_system(system))
+            }),
+            once: None,
+            edge: CallbackEdge::End,
+        }
+    }
+
+    /// Create a callback that runs `callback` as a command the first time it fires.
+    ///
+    /// No system is registered, so this is the simplest way to run a bit of
+    /// logic on completion. Later crossings, like those of a repeating
+    /// sequence, do nothing. Use [`AnimationCallback::new`] for callbacks
+    /// that should run every time or need system parameters.
+    pub fn once(callback: impl FnOnce(&mut World) + Send + Sync + 'static) -> Self {
+        Self {
+            system: DeferredSystem::none(),
+            once: Some(Box::new(callback)),
+            edge: CallbackEdge::End,
+        }
+    }
+
+    /// Set the edges at which this callback runs.
+    pub fn with_edge(mut self, edge: CallbackEdge) -> Self {
+        self.edge = edge;
+        self
+    }
 
     fn handle_movement(
-        q: Query<(&Self, &AnimationDuration, &PlayheadMove), Changed<PlayheadMove>>,
+        q: Query<
+            (
+                Entity,
+                &Self,
+                &AnimationDuration,
+                &PlayheadMove,
+                Option<&AnimationTarget>,
+            ),
+            Changed<PlayheadMove>,
+        >,
         mut commands: Commands,
     ) {
-        for (callback, duration, movement) in &q {
-            if movement.end >= duration.0.as_secs_f32() {
-                if let Some(id) = callback.system_id {
+        for (animation, callback, duration, movement, target) in &q {
+            let (end, start) = crossed_edges(duration, movement);
+            let run = match callback.edge {
+                CallbackEdge::End => end,
+                CallbackEdge::Start => start,
+                CallbackEdge::Both => end || start,
+            };
+
+            if !run {
+                continue;
+            }
+
+            match callback.system.id {
+                Some(CallbackSystem::Plain(id)) => {
                     commands.run_system(id);
                 }
+                Some(CallbackSystem::WithInput(id)) => {
+                    commands.run_system_with(
+                        id,
+                        CallbackContext {
+                            animation,
+                            target: target.map(|t| t.0),
+                            movement: *movement,
+                        },
+                    );
+                }
+                None if callback.once.is_some() => {
+                    commands.queue(move |world: &mut World| {
+                        let callback = world
+                            .get_mut::<Self>(animation)
+                            .and_then(|mut callback| callback.once.take());
+
+                        if let Some(callback) = callback {
+                            callback(world);
+                        }
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// Run a one-shot system whenever the playhead crosses this node.
+///
+/// An [`AnimationCallback`] runs on the edges chosen by its [`CallbackEdge`],
+/// the end moving forward by default. An `AnimationSystem` runs on both: when
+/// the playhead reaches the end moving forward or the start moving backward.
+/// This suits side effects in the middle of a sequence that should also
+/// respond to reversed playback or scrubbing.
+#[derive(Component)]
+#[require(AnimationDuration)]
+#[component(on_insert = <Self as DeferredSystemNode>::on_insert_hook)]
+pub struct AnimationSystem {
+    system: DeferredSystem<SystemId>,
+}
+
+impl DeferredSystemNode for AnimationSystem {
+    type Id = SystemId;
+
+    fn deferred_system(&mut self) -> &mut DeferredSystem<SystemId> {
+        &mut self.system
+    }
+}
+
+impl AnimationSystem {
+    pub fn new<S, M>(system: S) -> Self
+    where
+        S: IntoSystem<(), (), M> + Send + Sync + 'static,
+    {
+        Self {
+            system: DeferredSystem::new(move |world| world.register_system(system)),
+        }
+    }
+
+    fn handle_movement(
+        q: Query<(&Self, &AnimationDuration, &PlayheadMove), Changed<PlayheadMove>>,
+        mut commands: Commands,
+    ) {
+        for (system, duration, movement) in &q {
+            let (forward, backward) = crossed_edges(duration, movement);
+
+            if (forward || backward)
+                && let Some(id) = system.system.id
+            {
+                commands.run_system(id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{drivers::TimeDriver, testing::app};
+    use bevy_math::{EulerRot, Isometry2d, Isometry3d, Rot2, Vec3, Vec3A, Vec4};
+    use bevy_transform::components::Transform;
+
+    #[derive(Resource, Default)]
+    struct Fired(u32);
+
+    /// A value that can only be interpolated in place.
+    #[derive(Clone, Debug, PartialEq)]
+    struct InPlace(f32);
+
+    impl AnimationLerp for InPlace {
+        fn identity() -> Self {
+            Self(0.0)
+        }
+
+        fn animation_lerp(&self, _: &Self, _: f32) -> Self {
+            unreachable!("keyframes should interpolate into the field")
+        }
+
+        fn animation_lerp_into(&self, other: &Self, amount: f32, out: &mut Self) {
+            out.0 = self.0 + (other.0 - self.0) * amount;
+        }
+
+        fn difference(&self, other: &Self) -> Self {
+            Self(self.0 - other.0)
+        }
+
+        fn accumulate(&mut self, value: &Self) {
+            self.0 += value.0;
+        }
+    }
+
+    #[derive(Component)]
+    struct Gauge(InPlace);
+
+    #[test]
+    fn completion_applies_the_final_frame() {
+        let mut app = app(0.1);
+        app.init_resource::<Fired>();
+
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver::default(),
+                AnimationComplete::Despawn,
+                animations![
+                    (Keyframe(Vec3::X), AnimationDuration::secs(0.45)),
+                    AnimationCallback::new(|mut fired: ResMut<Fired>| fired.0 += 1),
+                ],
+            ))
+            .id();
+
+        for _ in 0..10_000 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert!(world.get_entity(root).is_err());
+        assert_eq!(world.get::<Transform>(target).unwrap().translation, Vec3::X);
+        assert_eq!(world.resource::<Fired>().0, 1);
+    }
+
+    #[test]
+    fn keyframes_interpolate_into_the_field() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Gauge(InPlace(0.0))).id();
+        app.world_mut().spawn((
+            AnimationTarget(target),
+            DynamicFieldLens::new(|gauge: &mut Gauge| &mut gauge.0),
+            TimeDriver::default(),
+            animations![(Keyframe(InPlace(2.0)), AnimationDuration::secs(0.45))],
+        ));
+
+        for _ in 0..10_000 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<Gauge>(target).unwrap().0, InPlace(2.0));
+    }
+
+    #[test]
+    fn shifts_land_on_their_captured_base_plus_the_shift() {
+        for observed in [false, true] {
+            let mut app = app(0.1);
+
+            let base = Vec3::new(1.0, 2.0, 3.0);
+            let target = app
+                .world_mut()
+                .spawn(Transform::from_translation(base))
+                .id();
+            let node = app
+                .world_mut()
+                .spawn((
+                    Shift(Vec3::new(0.0, 100.0, 0.0)),
+                    AnimationDuration::secs(1.0),
+                ))
+                .id();
+            if observed {
+                app.world_mut().entity_mut(node).insert(ObserverDriven);
             }
+            let root = app
+                .world_mut()
+                .spawn((
+                    AnimationTarget(target),
+                    lens!(Transform::translation),
+                    playhead::AnimationPlayhead::default(),
+                ))
+                .add_related::<AnimationOf>(&[node])
+                .id();
+
+            // Register the node's systems, then let them propagate its lens.
+            app.update();
+            app.update();
+
+            let translation = |app: &App| app.world().get::<Transform>(target).unwrap().translation;
+
+            step_animation(app.world_mut(), root, 0.5).unwrap();
+            assert!(translation(&app).abs_diff_eq(Vec3::new(1.0, 52.0, 3.0), 1e-4));
+
+            // The captured interval ignores changes made mid-play.
+            app.world_mut()
+                .get_mut::<Transform>(target)
+                .unwrap()
+                .translation = Vec3::ZERO;
+            step_animation(app.world_mut(), root, 0.5).unwrap();
+            assert_eq!(translation(&app), Vec3::new(1.0, 102.0, 3.0), "{observed}");
+        }
+    }
+
+    #[test]
+    fn zero_duration_callbacks_respect_their_edge() {
+        let mut app = app(0.1);
+        app.init_resource::<Fired>();
+        app.update();
+
+        let root = app
+            .world_mut()
+            .spawn((
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    Delay::secs(0.5),
+                    AnimationCallback::new(|mut fired: ResMut<Fired>| fired.0 += 1),
+                    AnimationCallback::new(|mut fired: ResMut<Fired>| fired.0 += 10)
+                        .with_edge(CallbackEdge::Start),
+                    Delay::secs(0.5),
+                ],
+            ))
+            .id();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 1);
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 11);
+    }
+
+    #[test]
+    fn zero_duration_events_fire_only_forward() {
+        #[derive(Event, Clone)]
+        struct Ping;
+
+        let mut app = app(0.1);
+        app.init_resource::<Fired>();
+        app.add_observer(|_: Trigger<Ping>, mut fired: ResMut<Fired>| fired.0 += 1);
+
+        let root = app
+            .world_mut()
+            .spawn((
+                playhead::AnimationPlayhead::default(),
+                animations![Delay::secs(0.5), AnimationEvent(Ping), Delay::secs(0.5)],
+            ))
+            .id();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 1);
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 1);
+    }
+
+    #[test]
+    fn animation_systems_run_on_reverse_sweeps() {
+        let mut app = app(0.1);
+        app.init_resource::<Fired>();
+
+        let root = app
+            .world_mut()
+            .spawn((
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    Delay::secs(0.5),
+                    AnimationSystem::new(|mut fired: ResMut<Fired>| fired.0 += 1),
+                    Delay::secs(0.5),
+                ],
+            ))
+            .id();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 1);
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        assert_eq!(app.world().resource::<Fired>().0, 2);
+    }
+
+    #[test]
+    fn anchored_deltas_stay_bounded_over_many_ping_pongs() {
+        let mut app = app(0.1);
+
+        let origin = Vec3::new(0.1, 0.2, 0.3);
+        let target = app
+            .world_mut()
+            .spawn(Transform::from_translation(origin))
+            .id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                playhead::AnimationPlayhead::default(),
+                animations![(
+                    Delta(Vec3::new(100.3, -7.7, 0.01)),
+                    DeltaBaseline,
+                    AnimationDuration::secs(1.0),
+                )],
+            ))
+            .id();
+
+        // Register the node's systems, then let them propagate its lens.
+        app.update();
+        app.update();
+
+        let translation = |app: &App| app.world().get::<Transform>(target).unwrap().translation;
+        for _ in 0..10_000 {
+            for delta in [0.25, 0.25, 0.25, 0.25, -0.25, -0.25, -0.25, -0.25] {
+                step_animation(app.world_mut(), root, delta).unwrap();
+            }
+        }
+        assert!(translation(&app).distance(origin) < 1e-4);
+
+        #[derive(Resource, Default)]
+        struct Drift(Vec3);
+
+        app.init_resource::<Drift>().add_observer(
+            |trigger: Trigger<DeltaDrift<Vec3>>, mut drift: ResMut<Drift>| {
+                drift.0 = trigger.drift;
+            },
+        );
+
+        app.world_mut()
+            .get_mut::<Transform>(target)
+            .unwrap()
+            .translation += Vec3::Y;
+        step_animation(app.world_mut(), root, 0.25).unwrap();
+
+        assert!(app.world().resource::<Drift>().0.distance(Vec3::Y) < 1e-4);
+    }
+
+    #[test]
+    fn deep_chains_propagate_without_overflowing() {
+        let mut app = app(0.1);
+
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                playhead::AnimationPlayhead::default(),
+            ))
+            .id();
+
+        let mut parent = root;
+        for _ in 0..10_000 {
+            parent = app.world_mut().spawn(AnimationOf(parent)).id();
+        }
+        let leaf = app
+            .world_mut()
+            .spawn((
+                AnimationOf(parent),
+                Keyframe(Vec3::X),
+                AnimationDuration::secs(1.0),
+            ))
+            .id();
+
+        // Register the node's systems, then let them propagate its lens.
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.get::<AnimationTarget>(leaf).unwrap().0, target);
+        assert_eq!(world.get::<AnimationLens<Vec3>>(leaf).unwrap().get(), root);
+        assert_eq!(
+            world.get::<playhead::SequenceDuration>(root).unwrap().0,
+            1.0
+        );
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert_eq!(translation, Vec3::X);
+    }
+
+    #[test]
+    fn whole_transforms_match_per_component_animation() {
+        let mut app = app(0.1);
+
+        let start = Transform::from_xyz(1.0, 2.0, 3.0).with_scale(Vec3::splat(0.5));
+        let end = Transform::from_xyz(-4.0, 0.0, 8.0)
+            .with_rotation(Quat::from_euler(EulerRot::XYZ, 0.3, 1.2, -0.7))
+            .with_scale(Vec3::new(2.0, 1.0, 3.0));
+
+        let whole = app.world_mut().spawn(start).id();
+        let whole_root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(whole),
+                DynamicFieldLens::new(|transform: &mut Transform| transform),
+                playhead::AnimationPlayhead::default(),
+                animations![(Keyframe(end), AnimationDuration::secs(1.0))],
+            ))
+            .id();
+
+        let split = app.world_mut().spawn(start).id();
+        let split_root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(split),
+                Animation::Parallel,
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    (
+                        lens!(Transform::translation),
+                        Keyframe(end.translation),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Transform::rotation),
+                        Keyframe(end.rotation),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Transform::scale),
+                        Keyframe(end.scale),
+                        AnimationDuration::secs(1.0),
+                    ),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        for _ in 0..4 {
+            step_animation(app.world_mut(), whole_root, 0.25).unwrap();
+            step_animation(app.world_mut(), split_root, 0.25).unwrap();
+
+            let whole = app.world().get::<Transform>(whole).unwrap();
+            let split = app.world().get::<Transform>(split).unwrap();
+            assert!(whole.translation.abs_diff_eq(split.translation, 1e-5));
+            assert!(whole.rotation.abs_diff_eq(split.rotation, 1e-5));
+            assert!(whole.scale.abs_diff_eq(split.scale, 1e-5));
+        }
+
+        let whole = app.world().get::<Transform>(whole).unwrap();
+        assert!(whole.translation.abs_diff_eq(end.translation, 1e-5));
+        assert!(whole.rotation.abs_diff_eq(end.rotation, 1e-5));
+        assert!(whole.scale.abs_diff_eq(end.scale, 1e-5));
+    }
+
+    /// Play `root` forward and back over its one second `cycles` times.
+    fn ping_pong(app: &mut App, root: Entity, cycles: usize) {
+        for _ in 0..cycles {
+            for delta in [0.3, 0.3, 0.4, -0.4, -0.3, -0.3] {
+                step_animation(app.world_mut(), root, delta).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn vec4_and_vec3a_deltas_accumulate_over_ping_pongs() {
+        #[derive(Component, Default)]
+        struct Uniforms {
+            color: Vec4,
+            position: Vec3A,
+        }
+
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Uniforms::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                Animation::Parallel,
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    (
+                        lens!(Uniforms::color),
+                        Delta(Vec4::ONE),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Uniforms::position),
+                        Delta(Vec3A::ONE),
+                        AnimationDuration::secs(1.0),
+                    ),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let uniforms = app.world().get::<Uniforms>(target).unwrap();
+        assert!(uniforms.color.abs_diff_eq(Vec4::ONE, 1e-5));
+        assert!(uniforms.position.abs_diff_eq(Vec3A::ONE, 1e-5));
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        ping_pong(&mut app, root, 100);
+
+        let uniforms = app.world().get::<Uniforms>(target).unwrap();
+        assert!(uniforms.color.abs_diff_eq(Vec4::ZERO, 1e-4));
+        assert!(uniforms.position.abs_diff_eq(Vec3A::ZERO, 1e-4));
+    }
+
+    #[test]
+    fn isometry_deltas_return_to_identity_over_ping_pongs() {
+        #[derive(Component, Default)]
+        struct Rig {
+            camera: Isometry3d,
+            sprite: Isometry2d,
+        }
+
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Rig::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                Animation::Parallel,
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    (
+                        lens!(Rig::camera),
+                        Delta(Isometry3d::new(
+                            Vec3::new(3.0, -1.0, 2.0),
+                            Quat::from_euler(EulerRot::XYZ, 0.4, -1.1, 0.9),
+                        )),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Rig::sprite),
+                        Delta(Isometry2d::new(Vec2::new(-2.0, 5.0), Rot2::radians(2.5))),
+                        AnimationDuration::secs(1.0),
+                    ),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let rig = app.world().get::<Rig>(target).unwrap();
+        assert!(
+            rig.camera
+                .translation
+                .abs_diff_eq(Vec3A::new(3.0, -1.0, 2.0), 1e-5)
+        );
+        assert!((rig.sprite.rotation.as_radians() - 2.5).abs() < 1e-5);
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        ping_pong(&mut app, root, 100);
+
+        let rig = app.world().get::<Rig>(target).unwrap();
+        assert!(rig.camera.translation.abs_diff_eq(Vec3A::ZERO, 1e-4));
+        assert!(rig.camera.rotation.abs_diff_eq(Quat::IDENTITY, 1e-4));
+        assert!(rig.sprite.translation.abs_diff_eq(Vec2::ZERO, 1e-4));
+        assert!(rig.sprite.rotation.angle_to(Rot2::IDENTITY).abs() < 1e-4);
+    }
+
+    #[test]
+    fn quat_deltas_return_to_identity_over_ping_pongs() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::rotation),
+                playhead::AnimationPlayhead::default(),
+                animations![(
+                    Delta(Quat::from_euler(EulerRot::XYZ, 0.4, -1.1, 2.9)),
+                    AnimationDuration::secs(1.0),
+                )],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let rotation = app.world().get::<Transform>(target).unwrap().rotation;
+        assert!(rotation.abs_diff_eq(Quat::from_euler(EulerRot::XYZ, 0.4, -1.1, 2.9), 1e-5));
+
+        step_animation(app.world_mut(), root, -1.0).unwrap();
+        ping_pong(&mut app, root, 100);
+
+        let rotation = app.world().get::<Transform>(target).unwrap().rotation;
+        assert!(rotation.abs_diff_eq(Quat::IDENTITY, 1e-4));
+    }
+
+    #[test]
+    fn overshooting_the_end_lands_exactly_on_it() {
+        let back_out = AnimationCurve::Ease(EaseFunction::BackOut);
+        let second = Duration::from_secs(1);
+        for curve in [None, Some(&back_out)] {
+            assert_eq!(get_time(second, 1.25, curve), 1.0);
+            assert_eq!(get_time(second, -0.25, curve), 0.0);
+        }
+
+        let mut app = app(0.1);
+        let world = app.world_mut();
+
+        let end = Vec3::new(0.3, 0.7, 1.1);
+        let target = world.spawn(Transform::default()).id();
+        let playhead = world
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                animations![(Keyframe(end), AnimationDuration::secs(1.0), back_out)],
+            ))
+            .id();
+
+        let lens = lens!(Transform::translation);
+        crate::testing::value_at(world, playhead, target, &lens, 0.9).unwrap();
+        let value = crate::testing::value_at(world, playhead, target, &lens, 1.6).unwrap();
+        assert_eq!(value, end);
+    }
+
+    #[test]
+    fn keyframes_recapture_only_when_leaving_the_start() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                playhead::AnimationPlayhead::default(),
+                animations![(Keyframe(Vec3::X * 10.0), AnimationDuration::secs(1.0))],
+            ))
+            .id();
+
+        // Register the node's systems, then let it propagate its lens.
+        app.update();
+        app.update();
+
+        let translation = |app: &App| app.world().get::<Transform>(target).unwrap().translation;
+        let nudge = |app: &mut App| {
+            app.world_mut()
+                .get_mut::<Transform>(target)
+                .unwrap()
+                .translation = Vec3::Y;
+        };
+
+        step_animation(app.world_mut(), root, 0.3).unwrap();
+        assert!(translation(&app).abs_diff_eq(Vec3::X * 3.0, 1e-5));
+
+        // Reversing partway and playing forward keeps the captured interval.
+        nudge(&mut app);
+        step_animation(app.world_mut(), root, -0.1).unwrap();
+        assert!(translation(&app).abs_diff_eq(Vec3::X * 2.0, 1e-5));
+        step_animation(app.world_mut(), root, 0.1).unwrap();
+        assert!(translation(&app).abs_diff_eq(Vec3::X * 3.0, 1e-5));
+
+        // Leaving the start again re-captures from the live value.
+        step_animation(app.world_mut(), root, -0.3).unwrap();
+        assert_eq!(translation(&app), Vec3::ZERO);
+        nudge(&mut app);
+        step_animation(app.world_mut(), root, 0.5).unwrap();
+        assert!(translation(&app).abs_diff_eq(Vec3::new(5.0, 0.5, 0.0), 1e-5));
+    }
+
+    #[test]
+    fn callbacks_can_spawn_and_step_animations() {
+        #[derive(Resource, Default)]
+        struct Chained(u32);
+
+        #[derive(Resource)]
+        struct Spawned(Entity);
+
+        let mut app = app(0.1);
+        app.init_resource::<Fired>().init_resource::<Chained>();
+
+        let world = app.world_mut();
+        let first = world.spawn(Transform::default()).id();
+        let second = world.spawn(Transform::default()).id();
+        let chained = world
+            .spawn((
+                AnimationTarget(second),
+                lens!(Transform::translation),
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    (Keyframe(Vec3::Y), AnimationDuration::secs(1.0)),
+                    AnimationCallback::new(|mut chained: ResMut<Chained>| chained.0 += 1),
+                ],
+            ))
+            .id();
+
+        let root = world
+            .spawn((
+                AnimationTarget(first),
+                lens!(Transform::translation),
+                playhead::AnimationPlayhead::default(),
+                animations![
+                    (Keyframe(Vec3::X), AnimationDuration::secs(0.5)),
+                    AnimationCallback::new(move |world: &mut World| {
+                        world.resource_mut::<Fired>().0 += 1;
+
+                        let target = world.spawn(Transform::default()).id();
+                        let spawned = world
+                            .spawn((
+                                AnimationTarget(target),
+                                lens!(Transform::translation),
+                                playhead::AnimationPlayhead::default(),
+                                animations![(Keyframe(Vec3::Z), AnimationDuration::secs(1.0))],
+                            ))
+                            .id();
+                        world.insert_resource(Spawned(spawned));
+
+                        step_animation(world, chained, 1.0).unwrap();
+                    }),
+                    (Keyframe(Vec3::X * 2.0), AnimationDuration::secs(0.5)),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        // The chained playhead is evaluated by the running pass, once.
+        step_animation(app.world_mut(), root, 0.6).unwrap();
+        let world = app.world();
+        assert_eq!(world.resource::<Fired>().0, 1);
+        assert_eq!(world.resource::<Chained>().0, 1);
+        let translation = world.get::<Transform>(first).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::X * 1.2, 1e-5));
+        assert_eq!(world.get::<Transform>(second).unwrap().translation, Vec3::Y);
+
+        step_animation(app.world_mut(), root, 0.4).unwrap();
+        let world = app.world();
+        assert_eq!(world.resource::<Fired>().0, 1);
+        assert_eq!(world.resource::<Chained>().0, 1);
+        assert_eq!(
+            world.get::<Transform>(first).unwrap().translation,
+            Vec3::X * 2.0
+        );
+
+        // The spawned animation plays like any other.
+        let spawned = app.world().resource::<Spawned>().0;
+        let target = app.world().get::<AnimationTarget>(spawned).unwrap().0;
+        app.update();
+        app.update();
+        step_animation(app.world_mut(), spawned, 1.0).unwrap();
+        assert_eq!(
+            app.world().get::<Transform>(target).unwrap().translation,
+            Vec3::Z
+        );
+    }
+
+    #[test]
+    fn a_single_step_jumps_at_the_midpoint() {
+        let mut app = app(0.1);
+        let world = app.world_mut();
+
+        let target = world.spawn(Transform::default()).id();
+        let playhead = world
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                animations![(
+                    Keyframe(Vec3::X),
+                    AnimationDuration::secs(1.0),
+                    AnimationCurve::steps(1),
+                )],
+            ))
+            .id();
+
+        let lens = lens!(Transform::translation);
+        for (time, expected) in [
+            (0.2, Vec3::ZERO),
+            (0.49, Vec3::ZERO),
+            (0.5, Vec3::X),
+            (0.8, Vec3::X),
+        ] {
+            let value = crate::testing::value_at(world, playhead, target, &lens, time).unwrap();
+            assert_eq!(value, expected, "at {time}s");
+        }
+
+        // More steps snap through evenly spaced values.
+        let steps = AnimationCurve::steps(4);
+        for (t, expected) in [(0.1, 0.0), (0.2, 0.25), (0.4, 0.5), (0.7, 0.75), (0.9, 1.0)] {
+            assert_eq!(steps.sample(t), expected, "at {t}");
         }
     }
 }
@@ -0,0 +1,226 @@
+use crate::{
+    AnimationCurve, AnimationDuration, AnimationTarget, DynamicFieldLens, FieldLens,
+    blend::FieldWriter,
+    get_time,
+    lens::{AnimationLens, FieldAccess, FieldGetter, Lensed},
+    lerp::AnimationLerp,
+    playhead::PlayheadMove,
+};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_platform::collections::hash_map::Entry;
+use std::marker::PhantomData;
+
+/// Multiply the "base" value of the animation target's lensed fields.
+///
+/// The base value is captured from the target when a modifier first
+/// applies to it. As the playhead sweeps over a modifier, the multiplier
+/// is interpolated from its previous value to this modifier's value.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(AnimationDuration)]
+pub struct Modifier(pub f32);
+
+/// The base values and current multipliers of a target's modified fields.
+///
+/// Fields are keyed by the entity holding their lens, so modifiers
+/// animating different fields of the same type don't share a base.
+#[derive(Component, Debug)]
+pub struct ModifierState<T: AnimationLerp> {
+    fields: EntityHashMap<ModifiedField<T>>,
+}
+
+#[derive(Debug)]
+struct ModifiedField<T> {
+    base: T,
+    factor: f32,
+}
+
+/// The multiplier captured when a modifier node became active.
+#[derive(Component, Debug)]
+pub struct ModifierStart<T: AnimationLerp> {
+    factor: f32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl Modifier {
+    pub(crate) fn handle_movement<T: AnimationLerp>(
+        modifiers: Query<
+            (
+                Entity,
+                &Self,
+                &AnimationDuration,
+                Option<&AnimationLens<T>>,
+                Has<Lensed>,
+                &AnimationTarget,
+                &PlayheadMove,
+                Option<&ModifierStart<T>>,
+                Option<&AnimationCurve>,
+            ),
+            Changed<PlayheadMove>,
+        >,
+        lens: Query<&DynamicFieldLens<T>>,
+        mut states: Query<&mut ModifierState<T>>,
+        mut target: Query<FieldGetter<T>>,
+        mut writer: FieldWriter<T>,
+        mut commands: Commands,
+    ) -> Result {
+        // Targets modified for the first time this run, inserted afterward.
+        let mut new_states = EntityHashMap::<ModifierState<T>>::default();
+
+        for (entity, modifier, duration, lens_ref, lensed, target_ref, movement, start, curve) in
+            &modifiers
+        {
+            // A lens of another type is evaluated by that type's system.
+            if lens_ref.is_none() && lensed {
+                continue;
+            }
+
+            // Zero-duration modifiers are a single point, so they should
+            // apply even though they never move.
+            if movement.start == movement.end && !duration.0.is_zero() {
+                continue;
+            }
+
+            let lens = AnimationLens::resolve(lens_ref, &lens, entity)?;
+            let mut target = FieldAccess::new(target.get_mut(target_ref.0)?);
+
+            let fields = match states.get_mut(target_ref.0) {
+                Ok(state) => &mut state.into_inner().fields,
+                Err(_) => {
+                    &mut new_states
+                        .entry(target_ref.0)
+                        .or_insert_with(|| ModifierState {
+                            fields: EntityHashMap::default(),
+                        })
+                        .fields
+                }
+            };
+            let state = match fields.entry(lens_ref.map_or(entity, AnimationLens::get)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(ModifiedField {
+                    base: lens.get_field(target.reborrow())?,
+                    factor: 1.0,
+                }),
+            };
+
+            let just_started = movement.enters_from_start();
+            let from = match (just_started, start) {
+                (false, Some(start)) => start.factor,
+                (true, _) | (false, None) => {
+                    commands.entity(entity).insert(ModifierStart::<T> {
+                        factor: state.factor,
+                        _marker: PhantomData,
+                    });
+
+                    state.factor
+                }
+            };
+
+            let t = get_time(duration.0, movement.end, curve);
+            state.factor = from.animation_lerp(&modifier.0, t);
+            writer.write(
+                lens,
+                entity,
+                target_ref.0,
+                target,
+                state.base.scale(state.factor),
+            )?;
+        }
+
+        for (target, state) in new_states {
+            commands.entity(target).insert(state);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Animation, animations, lens, playhead::step_animation, testing::app};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[derive(Component)]
+    struct Gauges {
+        a: f32,
+        b: f32,
+    }
+
+    #[test]
+    fn fields_of_the_same_type_keep_their_own_base() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Gauges { a: 2.0, b: 10.0 }).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                Animation::Parallel,
+                animations![
+                    (
+                        lens!(Gauges::a),
+                        Modifier(3.0),
+                        AnimationDuration::secs(1.0)
+                    ),
+                    (
+                        lens!(Gauges::b),
+                        Modifier(0.5),
+                        AnimationDuration::secs(1.0)
+                    ),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let gauges = app.world().get::<Gauges>(target).unwrap();
+        assert_eq!((gauges.a, gauges.b), (6.0, 5.0));
+    }
+
+    #[test]
+    fn modifiers_skip_lenses_of_other_types() {
+        let mut app = app(0.1);
+        let world = app.world_mut();
+        let gauges = world.spawn(Gauges { a: 2.0, b: 0.0 }).id();
+        world.spawn((AnimationTarget(gauges), lens!(Gauges::a)));
+
+        let target = world.spawn(Transform::from_translation(Vec3::ONE)).id();
+        let root = world
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                animations![(Modifier(2.0), AnimationDuration::secs(1.0))],
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert_eq!(translation, Vec3::splat(2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "neither it nor its ancestors")]
+    fn modifiers_without_a_lens_report_it() {
+        let mut app = app(0.1);
+        let world = app.world_mut();
+        let target = world.spawn(Gauges { a: 2.0, b: 0.0 }).id();
+        world.spawn((AnimationTarget(target), lens!(Gauges::a)));
+        let root = world
+            .spawn((
+                AnimationTarget(target),
+                animations![(Modifier(2.0), AnimationDuration::secs(1.0))],
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        step_animation(app.world_mut(), root, 1.0).unwrap();
+    }
+}
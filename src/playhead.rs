@@ -1,11 +1,21 @@
-use crate::Animate;
+use crate::{
+    Animate, AnimationComplete, ObserverDriven, diagnostics::KeyframeDiagnostics, report_cycle,
+};
 
-use super::{AnimationDuration, Animations};
-use bevy_ecs::prelude::*;
-use bevy_platform::collections::HashMap;
+use super::{
+    Animation, AnimationDuration, AnimationOf, AnimationSpeed, AnimationTarget, Animations,
+    StartOffset,
+};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_log::warn;
+use bevy_platform::collections::{HashMap, HashSet};
 
 #[derive(Resource, Default)]
-pub(super) struct PlayheadSteps(HashMap<usize, Vec<PlayheadStep>>);
+pub(super) struct PlayheadSteps {
+    stages: HashMap<usize, Vec<PlayheadStep>>,
+    /// Whether movements are currently being applied.
+    applying: bool,
+}
 
 struct PlayheadStep {
     playhead: Entity,
@@ -15,25 +25,365 @@ struct PlayheadStep {
     movement: PlayheadMove,
 }
 
+/// Advance a playhead by `delta` seconds and evaluate the animations it sweeps over.
+///
+/// This runs the same passes as the [`KeyframePlugin`](crate::KeyframePlugin)
+/// does in `PreUpdate`, but for a single playhead and without consulting its
+/// driver, which is useful for tests and headless tools. The plugin must
+/// still be added and the app updated once, so the systems for each animated
+/// type are registered. Fields with a [`BlendMode`](crate::BlendMode) are
+/// only resolved in `PreUpdate`.
+///
+/// When called while animations are already being evaluated, like from an
+/// [`AnimationCallback`](crate::AnimationCallback), the sweep is queued and
+/// evaluated by the running pass once its current stage finishes.
+pub fn step_animation(world: &mut World, playhead: Entity, delta: f32) -> Result {
+    world.run_system_cached(SequenceLayout::update)?;
+
+    world.resource_scope(|world, mut steps: Mut<PlayheadSteps>| -> Result {
+        let mut query = world.query::<(&mut AnimationPlayhead, &SequenceLayout)>();
+        let (mut state, layout) = query.get_mut(world, playhead)?;
+
+        *state.get_mut() += delta;
+        let Some((previous, current)) = state.sweep(playhead, layout, &mut steps) else {
+            return Ok(());
+        };
+
+        let total = layout.total;
+        let crossed: Vec<_> = world
+            .get::<ProgressMarkers>(playhead)
+            .map(|markers| markers.crossed(previous, current, total).collect())
+            .unwrap_or_default();
+        for fraction in crossed {
+            world.trigger_targets(ProgressMarker { fraction }, playhead);
+        }
+
+        Ok(())
+    })?;
+
+    AnimationPlayhead::apply_movement(world)
+}
+
+/// Move a playhead to `position`, or leave it where it is, and evaluate
+/// any animations it hasn't yet swept over.
+///
+/// Unlike [`step_animation`], this doesn't trigger any [`SequenceEvent`]s
+/// or [`ProgressMarker`]s, so drivers and completion policies don't respond.
+pub(crate) fn settle_playhead(
+    world: &mut World,
+    playhead: Entity,
+    position: Option<f32>,
+) -> Result {
+    world.run_system_cached(SequenceLayout::update)?;
+
+    world.resource_scope(|world, mut steps: Mut<PlayheadSteps>| -> Result {
+        let mut query = world.query::<(&mut AnimationPlayhead, &SequenceLayout)>();
+        let (mut state, layout) = query.get_mut(world, playhead)?;
+
+        if let Some(position) = position {
+            state.seek_to(position);
+        }
+        state.sweep(playhead, layout, &mut steps);
+
+        for step in steps.stages.values_mut().flatten() {
+            if step.playhead == playhead {
+                step.start = false;
+                step.end = false;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    AnimationPlayhead::apply_movement(world)
+}
+
 #[derive(Component, Debug, Default)]
+#[require(SequenceLayout)]
 pub struct AnimationPlayhead {
     playhead: f32,
     previous_position: f32,
 }
 
+/// Commands for moving an entity's [`AnimationPlayhead`].
+pub trait PlayheadCommands {
+    /// Queue an [`AnimationPlayhead::seek_to`].
+    fn seek_animation(&mut self, playhead: f32) -> &mut Self;
+
+    /// Queue an [`AnimationPlayhead::jump_to`].
+    fn jump_animation(&mut self, playhead: f32) -> &mut Self;
+}
+
+impl PlayheadCommands for EntityCommands<'_> {
+    fn seek_animation(&mut self, playhead: f32) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            if let Some(mut head) = entity.get_mut::<AnimationPlayhead>() {
+                head.seek_to(playhead);
+            }
+        })
+    }
+
+    fn jump_animation(&mut self, playhead: f32) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            if let Some(mut head) = entity.get_mut::<AnimationPlayhead>() {
+                head.jump_to(playhead);
+            }
+        })
+    }
+}
+
+/// The structure of an animation hierarchy.
+pub type AnimationNodes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static Animations>,
+        Option<&'static Animation>,
+        Option<&'static AnimationDuration>,
+        Option<&'static AnimationSpeed>,
+        Option<&'static StartOffset>,
+        Has<PlayheadDriver>,
+    ),
+>;
+
+/// Marks a node whose playhead is driven directly, like by a
+/// [`TimeDriver`](crate::drivers::TimeDriver).
+///
+/// Ancestors' playheads skip over the node's subtree, so it runs
+/// independently of them while still animating the same target.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[require(AnimationPlayhead)]
+pub struct PlayheadDriver;
+
+/// The children of a node that follow its playhead.
+fn followers<'a>(
+    children: &'a Animations,
+    nodes: &'a AnimationNodes,
+) -> impl Iterator<Item = Entity> + 'a {
+    children
+        .iter()
+        .filter(|child| !nodes.get(*child).is_ok_and(|(.., driven)| driven))
+}
+
+/// The [`StartOffset`] of a parallel node's child in seconds.
+fn start_offset(child: Entity, nodes: &AnimationNodes) -> f32 {
+    nodes
+        .get(child)
+        .ok()
+        .and_then(|(.., offset, _)| offset)
+        .map_or(0.0, |offset| offset.0.as_secs_f32())
+}
+
+/// The total duration of an animation hierarchy in seconds.
+///
+/// This is kept up to date on the root of each hierarchy, and on each
+/// [`PlayheadDriver`] node, whenever the hierarchy or any of its durations change.
+/// It mirrors [`SequenceLayout::total`].
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct SequenceDuration(pub f32);
+
+/// The progress of an animation hierarchy's playhead, from `0.0` to `1.0`.
+///
+/// Insert this alongside an [`AnimationPlayhead`] to keep it updated each frame.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+#[require(AnimationPlayhead)]
+pub struct AnimationProgress(pub f32);
+
+impl AnimationProgress {
+    pub(super) fn update(mut q: Query<(&AnimationPlayhead, Option<&SequenceDuration>, &mut Self)>) {
+        for (playhead, duration, mut progress) in &mut q {
+            let total = duration.map(|d| d.0).unwrap_or_default();
+            progress.set_if_neq(Self(playhead.progress(total)));
+        }
+    }
+}
+
+/// The playheads animating each target.
+///
+/// This maps each [`AnimationTarget`] to the playhead entities that target it,
+/// so inspectors can find an entity's animations without visiting every playhead.
+/// Only the playheads that are actually played are indexed, meaning roots and
+/// [`PlayheadDriver`] nodes, and only by the target on their own entity, not
+/// targets overridden further down their hierarchy. The index is updated in
+/// [`AnimationSystems::Playhead`](crate::AnimationSystems::Playhead).
+///
+/// [`AnimationsTargeting`] pairs each playhead with its progress.
+#[derive(Resource, Debug, Default)]
+pub struct AnimationTargetIndex {
+    playheads: HashMap<Entity, HashSet<Entity>>,
+    targets: HashMap<Entity, Entity>,
+}
+
+impl AnimationTargetIndex {
+    /// Return the playheads whose [`AnimationTarget`] is `target`.
+    pub fn playheads(&self, target: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.playheads.get(&target).into_iter().flatten().copied()
+    }
+
+    /// Return the target of `playhead`, if it's in the index.
+    pub fn target(&self, playhead: Entity) -> Option<Entity> {
+        self.targets.get(&playhead).copied()
+    }
+
+    fn remove(&mut self, playhead: Entity) {
+        let Some(target) = self.targets.remove(&playhead) else {
+            return;
+        };
+
+        if let Some(playheads) = self.playheads.get_mut(&target) {
+            playheads.remove(&playhead);
+            if playheads.is_empty() {
+                self.playheads.remove(&target);
+            }
+        }
+    }
+
+    pub(super) fn update(
+        mut index: ResMut<Self>,
+        changed: Query<
+            (Entity, &AnimationTarget),
+            (
+                With<AnimationPlayhead>,
+                Or<(Without<AnimationOf>, With<PlayheadDriver>)>,
+                Or<(
+                    Changed<AnimationTarget>,
+                    Added<AnimationPlayhead>,
+                    Added<PlayheadDriver>,
+                )>,
+            ),
+        >,
+        mut removed_targets: RemovedComponents<AnimationTarget>,
+        mut removed_playheads: RemovedComponents<AnimationPlayhead>,
+        mut removed_parents: RemovedComponents<AnimationOf>,
+        reparented: Query<Entity, (Added<AnimationOf>, Without<PlayheadDriver>)>,
+        current: Query<
+            &AnimationTarget,
+            (
+                With<AnimationPlayhead>,
+                Or<(Without<AnimationOf>, With<PlayheadDriver>)>,
+            ),
+        >,
+    ) {
+        // Components may be removed and re-inserted within a frame,
+        // so we only drop entities that are no longer complete.
+        for playhead in removed_targets
+            .read()
+            .chain(removed_playheads.read())
+            .chain(&reparented)
+        {
+            if !current.contains(playhead) {
+                index.remove(playhead);
+            }
+        }
+
+        // Nodes detached from their parents become roots.
+        let detached = removed_parents
+            .read()
+            .filter_map(|playhead| Some((playhead, current.get(playhead).ok()?)));
+
+        for (playhead, target) in changed.iter().chain(detached) {
+            index.remove(playhead);
+            index.targets.insert(playhead, target.0);
+            index
+                .playheads
+                .entry(target.0)
+                .or_default()
+                .insert(playhead);
+        }
+    }
+}
+
+/// The playheads animating each entity, along with their progress.
+///
+/// This looks playheads up through the [`AnimationTargetIndex`], so it
+/// doesn't visit every playhead.
+///
+/// ```ignore
+/// fn inspect(animations: AnimationsTargeting, selected: Single<Entity, With<Selected>>) {
+///     for (playhead, progress) in animations.progress(*selected) {
+///         info!("{playhead}: {:.0}%", progress * 100.0);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct AnimationsTargeting<'w, 's> {
+    index: Res<'w, AnimationTargetIndex>,
+    playheads: Query<'w, 's, (&'static AnimationPlayhead, &'static SequenceLayout)>,
+}
+
+impl AnimationsTargeting<'_, '_> {
+    /// Return each playhead animating `target` with its progress, from `0.0` to `1.0`.
+    pub fn progress(&self, target: Entity) -> impl Iterator<Item = (Entity, f32)> + '_ {
+        self.index.playheads(target).filter_map(|entity| {
+            let (playhead, layout) = self.playheads.get(entity).ok()?;
+            Some((entity, playhead.progress(layout.total())))
+        })
+    }
+}
+
+/// The playhead's movement within a leaf, in seconds from the leaf's start.
+///
+/// Zero-duration leaves receive a `0.0` to `0.0` movement
+/// once each time the playhead crosses them.
 #[derive(Event, Component, Debug, Clone, Copy)]
 pub struct PlayheadMove {
     pub start: f32,
     pub end: f32,
+    /// Whether the playhead moved backward, which zero-duration
+    /// leaves can't tell from `start` and `end`.
+    pub reversed: bool,
+}
+
+impl PlayheadMove {
+    /// Whether this movement leaves the very start of the leaf moving forward.
+    ///
+    /// This is the only point at which nodes re-capture the values they
+    /// start from. Any other movement, including reversing partway
+    /// through and playing forward again, keeps the captured values.
+    pub fn enters_from_start(&self) -> bool {
+        self.start == 0.0 && self.end > 0.0
+    }
 }
 
+/// Events in the lifetime of a playhead's sequence.
+///
+/// These are triggered on the entity holding the [`AnimationPlayhead`], so a
+/// particular animation can be observed directly:
+///
+/// ```ignore
+/// commands.entity(playhead).observe(|trigger: Trigger<SequenceEvent>| {
+///     if *trigger == SequenceEvent::SequenceCompleted {
+///         info!("{} completed", trigger.target());
+///     }
+/// });
+/// ```
 #[derive(Event, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum SequenceEvent {
+    /// The playhead left the start of the sequence.
     SequenceStarted,
+    /// The playhead reached the end of the sequence, or the start when reversed.
     SequenceCompleted,
+    /// A repeating sequence crossed a loop boundary.
+    SequenceLooped {
+        /// The number of times the sequence has completed.
+        loops: u32,
+    },
 }
 
 impl AnimationPlayhead {
+    /// A playhead that starts at `playhead` seconds into its sequence.
+    ///
+    /// Like [`AnimationPlayhead::jump_to`], the animations before this
+    /// position aren't evaluated. Inserting this when spawning replaces the
+    /// playhead required by the animation components, and nothing else
+    /// moves it until its driver does.
+    pub fn at(playhead: f32) -> Self {
+        Self {
+            playhead,
+            previous_position: playhead,
+        }
+    }
+
     pub fn get(&self) -> f32 {
         self.playhead
     }
@@ -46,12 +396,44 @@ impl AnimationPlayhead {
         self.playhead = playhead;
     }
 
+    /// Return the playhead's position as a fraction of `total` seconds, from `0.0` to `1.0`.
+    ///
+    /// A sequence without any duration is always complete, so this returns `1.0`.
+    pub fn progress(&self, total: f32) -> f32 {
+        if total <= 0.0 {
+            return 1.0;
+        }
+
+        (self.playhead / total).clamp(0.0, 1.0)
+    }
+
+    /// Set the playhead to a fraction of `total` seconds, from `0.0` to `1.0`.
+    ///
+    /// This is the counterpart of [`AnimationPlayhead::progress`], and
+    /// like [`AnimationPlayhead::set`], the animations swept over are
+    /// evaluated. Fractions outside the sequence are clamped.
+    pub fn set_normalized(&mut self, fraction: f32, total: f32) {
+        self.set(fraction.clamp(0.0, 1.0) * total.max(0.0));
+    }
+
     /// Move the playhead to a position without triggering any side-effects.
+    ///
+    /// To evaluate everything between the current and new position, use
+    /// [`AnimationPlayhead::seek_to`].
     pub fn jump_to(&mut self, playhead: f32) {
         self.playhead = playhead;
         self.previous_position = playhead;
     }
 
+    /// Move the playhead to a position, evaluating all animations swept
+    /// over as if time had advanced there.
+    ///
+    /// To skip over animations without evaluating them, use
+    /// [`AnimationPlayhead::jump_to`].
+    pub fn seek_to(&mut self, playhead: f32) {
+        self.playhead = playhead;
+    }
+
     /// Return the previous playhead position.
     ///
     /// This advances the stored previous position to the current playhead.
@@ -66,18 +448,34 @@ impl AnimationPlayhead {
     // animation schedule more than once per frame. This preserves the order of
     // segments while avoiding severe performance penalties from mechanisms like
     // observer events.
+    //
+    // Systems in the schedule, like callbacks, may move playheads themselves
+    // through `step_animation`. Rather than running the schedule re-entrantly,
+    // only the outermost pass applies movements, and it picks up any steps
+    // queued along the way after the stage that queued them.
     pub(super) fn apply_movement(world: &mut World) -> Result {
-        let stages = world
-            .resource::<PlayheadSteps>()
-            .0
-            .keys()
-            .max()
-            .copied()
-            .map(|s| s + 1)
-            .unwrap_or(0);
+        let mut steps = world.resource_mut::<PlayheadSteps>();
+        if steps.applying {
+            return Ok(());
+        }
+        steps.applying = true;
+
+        let result = Self::apply_stages(world);
+        world.resource_mut::<PlayheadSteps>().applying = false;
+
+        result
+    }
 
-        for stage in 0..stages {
-            let Some(items) = world.resource_mut::<PlayheadSteps>().0.remove(&stage) else {
+    fn apply_stages(world: &mut World) -> Result {
+        // Each step of a starting sweep is flagged, but the
+        // sequence should only report starting once.
+        let mut started = HashSet::new();
+        loop {
+            let mut steps = world.resource_mut::<PlayheadSteps>();
+            let Some(stage) = steps.stages.keys().min().copied() else {
+                return Ok(());
+            };
+            let Some(mut items) = steps.stages.remove(&stage) else {
                 continue;
             };
 
@@ -85,6 +483,11 @@ impl AnimationPlayhead {
                 continue;
             }
 
+            // Steps are pushed in query order, which can vary between runs,
+            // so we sort them to apply the same inputs in the same order.
+            items.sort_unstable_by_key(|step| (step.playhead, step.entity));
+
+            let mut applied = 0;
             for PlayheadStep {
                 playhead,
                 start,
@@ -93,144 +496,726 @@ impl AnimationPlayhead {
                 movement,
             } in items
             {
-                world.get_entity_mut(entity)?.insert(movement);
+                // The sequence starts before any of its leaves are evaluated.
+                if start
+                    && started.insert(playhead)
+                    && let Ok(mut playhead) = world.get_entity_mut(playhead)
+                {
+                    playhead.trigger(SequenceEvent::SequenceStarted);
+                }
 
-                if start || end {
-                    let mut playhead = world.get_entity_mut(playhead)?;
+                // A leaf that only touches the playhead at one of its edges
+                // has nothing to evaluate, so we leave its movement untouched
+                // rather than waking every handler. Zero-duration leaves are
+                // always evaluated, since touching them is how they fire.
+                // Completing an earlier stage may have despawned the node.
+                let Ok(mut node) = world.get_entity_mut(entity) else {
+                    continue;
+                };
+                let empty = movement.start == movement.end
+                    && node
+                        .get::<AnimationDuration>()
+                        .is_some_and(|duration| !duration.0.is_zero());
 
-                    if start {
-                        playhead.trigger(SequenceEvent::SequenceStarted);
-                    }
-                    if end {
-                        playhead.trigger(SequenceEvent::SequenceCompleted);
+                if !empty {
+                    applied += 1;
+                    if node.contains::<ObserverDriven>() {
+                        node.trigger(movement);
+                    } else {
+                        node.insert(movement);
                     }
                 }
+
+                if end && let Ok(mut playhead) = world.get_entity_mut(playhead) {
+                    playhead.trigger(SequenceEvent::SequenceCompleted);
+                }
             }
 
             world.try_schedule_scope(Animate, |world, schedule| {
                 schedule.run(world);
             })?;
+            KeyframeDiagnostics::record_run(world, applied);
+            AnimationComplete::apply_pending(world);
         }
-
-        Ok(())
     }
 
-    // This is pretty inefficient, but could be easily improved with some caching.
-    //
-    // We essentially sweep over the entire `Animations` hierarchy, building up the timeline
-    // as we go. If we've swept over any leaves, we keep track of them for the `apply_movement`
-    // system. This results in okayish performane over thousands of hierarchies.
+    // We sweep over the playhead's cached `SequenceLayout`. If we've swept over any
+    // leaves, we keep track of them for the `apply_movement` system.
     pub(super) fn handle_movement(
-        mut playheads: Query<(Entity, &mut Self), Changed<Self>>,
-        animation_leaves: Query<&Animations>,
-        animations: Query<&AnimationDuration>,
+        mut playheads: Query<
+            (Entity, &mut Self, &SequenceLayout, Option<&ProgressMarkers>),
+            Changed<Self>,
+        >,
         mut steps: ResMut<PlayheadSteps>,
-    ) -> Result {
-        for (playhead_entity, mut playhead) in &mut playheads {
-            let previous_position = playhead.advance();
-            let difference = playhead.get() - previous_position;
+        mut commands: Commands,
+    ) {
+        for (entity, mut playhead, layout, markers) in &mut playheads {
+            let Some((previous, current)) = playhead.sweep(entity, layout, &mut steps) else {
+                continue;
+            };
 
-            if difference > 0.0 {
-                // find the animation node
-                let playhead_instant = playhead.get();
-                let mut time = 0f32;
-                let mut step = 0;
+            for fraction in markers
+                .iter()
+                .flat_map(|m| m.crossed(previous, current, layout.total))
+            {
+                commands.trigger_targets(ProgressMarker { fraction }, entity);
+            }
+        }
+    }
 
-                let mut leaves = animation_leaves.iter_leaves(playhead_entity).peekable();
+    /// Queue the movement of every leaf swept over, returning
+    /// the previous and current positions if the playhead moved.
+    fn sweep(
+        &mut self,
+        playhead_entity: Entity,
+        layout: &SequenceLayout,
+        steps: &mut PlayheadSteps,
+    ) -> Option<(f32, f32)> {
+        let previous_position = self.advance();
+        let playhead_instant = self.get();
 
-                while let Some(leaf) = leaves.next() {
-                    let duration = animations.get(leaf)?;
+        if previous_position == playhead_instant {
+            return None;
+        }
 
-                    let duration = duration.0.as_secs_f32();
+        let spans = &layout.spans;
+        let total = layout.total;
+        let forward = playhead_instant > previous_position;
 
-                    let node_start = time;
-                    let node_end = node_start + duration;
+        // Collect every leaf whose range has been swept over, in the
+        // order the playhead encountered them.
+        let (low, high) = if forward {
+            (previous_position, playhead_instant)
+        } else {
+            (playhead_instant, previous_position)
+        };
 
-                    // If true, some part of the range occupied by this node has been
-                    // swept over.
-                    if previous_position <= node_end {
-                        let start = (previous_position - node_start).max(0.0);
-                        let end = (playhead_instant - node_start).min(duration);
+        // Leaves are sorted by start, and `reach` never decreases, so
+        // only the leaves between these bounds can overlap the sweep.
+        let first = spans.partition_point(|span| span.reach < low);
+        let last = spans.partition_point(|span| span.start <= high);
+        let candidates = &spans[first..last];
 
-                        // The playhead move does not overlap this node.
-                        if playhead_instant < node_start {
-                            break;
-                        }
+        let swept: Vec<_> = if forward {
+            candidates
+                .iter()
+                .filter(|span| {
+                    if span.duration == 0.0 {
+                        crosses_point(span.start, previous_position, playhead_instant, total)
+                    } else {
+                        previous_position <= span.start + span.duration
+                            && playhead_instant >= span.start
+                    }
+                })
+                .collect()
+        } else {
+            candidates
+                .iter()
+                .rev()
+                .filter(|span| {
+                    if span.duration == 0.0 {
+                        crosses_point(span.start, previous_position, playhead_instant, total)
+                    } else {
+                        previous_position >= span.start
+                            && playhead_instant <= span.start + span.duration
+                    }
+                })
+                .collect()
+        };
+
+        // Every step of a sweep that leaves the start is flagged as starting,
+        // but only the last step of one that reaches the end is flagged as ending.
+        let (started, ended) = if forward {
+            (
+                previous_position == 0.0,
+                previous_position < total && playhead_instant >= total,
+            )
+        } else {
+            (
+                previous_position >= total,
+                previous_position > 0.0 && playhead_instant <= 0.0,
+            )
+        };
+
+        let last = swept.len().saturating_sub(1);
+        for (step, span) in swept.into_iter().enumerate() {
+            let start = span.local_time(previous_position);
+            let end = span.local_time(playhead_instant);
+
+            steps.stages.entry(step).or_default().push(PlayheadStep {
+                playhead: playhead_entity,
+                start: started,
+                end: ended && step == last,
+                entity: span.entity,
+                movement: PlayheadMove {
+                    start,
+                    end,
+                    reversed: !forward,
+                },
+            });
+        }
+
+        Some((previous_position, playhead_instant))
+    }
+}
+
+/// Whether moving from `previous` to `current` crosses a single point on a
+/// timeline lasting `total` seconds.
+///
+/// Points are crossed only when the playhead leaves or passes them, in either
+/// direction. A movement that stops exactly on a point defers it to the next
+/// movement, unless the point is the end of the timeline in that direction,
+/// where there's no next movement.
+fn crosses_point(point: f32, previous: f32, current: f32, total: f32) -> bool {
+    if current > previous {
+        (previous <= point && point < current) || (point == current && point >= total)
+    } else {
+        (current < point && point <= previous) || (point == current && point <= 0.0)
+    }
+}
+
+/// Fractions of a playhead's sequence, from `0.0` to `1.0`, that trigger a
+/// [`ProgressMarker`] on the playhead's entity when crossed in either direction.
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct ProgressMarkers(pub Vec<f32>);
 
-                        let started = previous_position == 0.0;
-                        let ended = playhead_instant >= node_end && leaves.peek().is_none();
+impl ProgressMarkers {
+    /// The markers crossed by a movement, in the order they were crossed.
+    fn crossed(&self, previous: f32, current: f32, total: f32) -> impl Iterator<Item = f32> {
+        let mut crossed: Vec<_> = self
+            .0
+            .iter()
+            .copied()
+            .filter(|fraction| crosses_point(fraction * total, previous, current, total))
+            .collect();
+
+        crossed.sort_by(f32::total_cmp);
+        if current < previous {
+            crossed.reverse();
+        }
+
+        crossed.into_iter()
+    }
+}
 
-                        steps.0.entry(step).or_default().push(PlayheadStep {
-                            playhead: playhead_entity,
-                            start: started,
-                            end: ended,
-                            entity: leaf,
-                            movement: PlayheadMove { start, end },
-                        });
+/// Triggered on a playhead's entity when it crosses one of its [`ProgressMarkers`].
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ProgressMarker {
+    /// The fraction of the sequence that was crossed.
+    pub fraction: f32,
+}
 
-                        step += 1;
+/// The leaves under a playhead, placed on its timeline.
+///
+/// This is rebuilt whenever the hierarchy or any of its durations change,
+/// so the playhead doesn't need to walk the hierarchy as it moves.
+#[derive(Component, Debug, Default)]
+pub struct SequenceLayout {
+    /// Sorted by start time.
+    spans: Vec<LeafSpan>,
+    total: f32,
+}
 
-                        // If true, the playhead stopped within this node's range.
-                        if playhead_instant < node_end {
-                            break;
+impl SequenceLayout {
+    /// The duration of the playhead's sequence in seconds.
+    pub fn total(&self) -> f32 {
+        self.total
+    }
+
+    pub(super) fn update(
+        changed: Query<
+            Entity,
+            Or<(
+                Changed<Animations>,
+                Changed<Animation>,
+                Changed<AnimationDuration>,
+                Changed<AnimationSpeed>,
+                Changed<StartOffset>,
+                Added<AnimationPlayhead>,
+                Added<PlayheadDriver>,
+            )>,
+        >,
+        mut emptied: RemovedComponents<Animations>,
+        parents: Query<&AnimationOf>,
+        nodes: AnimationNodes,
+        mut layouts: Query<&mut SequenceLayout>,
+        mut commands: Commands,
+    ) {
+        // Only roots and independently driven nodes are played, so only the
+        // nearest one at or above each changed node needs a new layout.
+        let mut visited = HashSet::new();
+        let mut dirty = Vec::new();
+        for mut node in changed.iter().chain(emptied.read()) {
+            while visited.insert(node) {
+                let driven = nodes.get(node).is_ok_and(|(.., driven)| driven);
+                match parents.get(node) {
+                    Ok(parent) if !driven => node = parent.0,
+                    _ => {
+                        if layouts.contains(node) {
+                            dirty.push(node);
                         }
+                        break;
                     }
-
-                    time += duration;
                 }
-            } else if difference < 0.0 {
-                // find the animation node
-                let playhead_instant = playhead.get();
-                let mut time = 0f32;
+            }
+        }
 
-                let mut swept_leaves = Vec::new();
-                let mut first_leaf = true;
+        for entity in dirty {
+            let Ok(mut sequence) = layouts.get_mut(entity) else {
+                continue;
+            };
+            let sequence = &mut *sequence;
 
-                for leaf in animation_leaves.iter_leaves(playhead_entity) {
-                    let duration = animations.get(leaf)?;
+            sequence.spans.clear();
+            sequence.total = layout(entity, &nodes, &mut sequence.spans);
+            commands
+                .entity(entity)
+                .insert(SequenceDuration(sequence.total));
+            sequence.spans.sort_by(|a, b| a.start.total_cmp(&b.start));
 
-                    let duration = duration.0.as_secs_f32();
+            let mut reach = f32::NEG_INFINITY;
+            for span in &mut sequence.spans {
+                reach = reach.max(span.start + span.duration);
+                span.reach = reach;
+            }
+        }
+    }
+}
 
-                    let node_start = time;
-                    let node_end = node_start + duration;
+/// A leaf's placement on its sequence's timeline.
+#[derive(Debug)]
+struct LeafSpan {
+    entity: Entity,
+    start: f32,
+    /// The time the leaf occupies on the timeline.
+    duration: f32,
+    /// The rate of the leaf's own time relative to the timeline.
+    speed: f32,
+    /// The leaf's own duration.
+    length: f32,
+    /// The latest end of this or any earlier leaf.
+    reach: f32,
+}
 
-                    // If true, some part of the range occupied by this node has been
-                    // swept over.
-                    if previous_position <= node_end && previous_position > node_start {
-                        let start = (previous_position - node_start).max(0.0);
-                        let end = (playhead_instant - node_start).clamp(0.0, duration);
+impl LeafSpan {
+    /// Convert a time on the timeline to the leaf's own time.
+    fn local_time(&self, instant: f32) -> f32 {
+        // Rescaling can fall just short of the end, which
+        // would keep nodes from ever reaching it.
+        if instant >= self.start + self.duration {
+            return self.length;
+        }
 
-                        // The playhead move does not overlap this node.
-                        if playhead_instant > node_end {
-                            time += duration;
-                            continue;
-                        }
+        ((instant - self.start) * self.speed).clamp(0.0, self.length)
+    }
+}
 
-                        swept_leaves.push((first_leaf, previous_position, start, end, leaf));
-                    }
+/// Return a node's children, treating [`Animation::Leaf`] nodes as childless.
+fn leaf_children<'a>(
+    children: Option<&'a Animations>,
+    animation: Option<&Animation>,
+) -> Option<&'a Animations> {
+    children.filter(|_| animation != Some(&Animation::Leaf))
+}
+
+/// Place every leaf under `root` on the timeline, returning its duration.
+///
+/// Children of [`Animation::Parallel`] nodes all start with their parent, shifted
+/// by any [`StartOffset`], while other nodes' children start when the previous
+/// child ends. Each node's time is scaled by every [`AnimationSpeed`] above it.
+///
+/// The hierarchy is walked with an explicit stack, so arbitrarily deep
+/// hierarchies can't overflow. Nodes that are visited twice mean the hierarchy
+/// loops back on itself, so they're skipped with an error rather than laid
+/// out forever.
+fn layout(root: Entity, nodes: &AnimationNodes, spans: &mut Vec<LeafSpan>) -> f32 {
+    let mut visited = HashSet::new();
+    let mut stack = Vec::new();
+    let mut finished = match LayoutGroup::enter(root, 0.0, 1.0, nodes, spans, &mut visited) {
+        Ok(duration) => return duration,
+        Err(group) => {
+            stack.push(group);
+            None
+        }
+    };
+
+    while let Some(group) = stack.last_mut() {
+        if let Some(duration) = finished.take() {
+            group.finish_child(duration);
+        }
+
+        let Some(&child) = group.children.get(group.next) else {
+            finished = Some(group.extent);
+            stack.pop();
+            continue;
+        };
+        group.next += 1;
+
+        let offset = if group.parallel {
+            // Offsets are in the group's own time, so they scale with it.
+            group.delay = start_offset(child, nodes) / group.speed;
+            group.start + group.delay
+        } else {
+            group.start + group.extent
+        };
+
+        match LayoutGroup::enter(child, offset, group.speed, nodes, spans, &mut visited) {
+            Ok(duration) => finished = Some(duration),
+            Err(child) => stack.push(child),
+        }
+    }
+
+    finished.unwrap_or_default()
+}
+
+/// A node with children that's partway through being laid out.
+struct LayoutGroup {
+    start: f32,
+    speed: f32,
+    parallel: bool,
+    children: Vec<Entity>,
+    /// The index of the next child to lay out.
+    next: usize,
+    /// The [`StartOffset`] of the child being laid out.
+    delay: f32,
+    /// The time the group's children occupy so far.
+    extent: f32,
+}
 
-                    time += duration;
-                    first_leaf = false;
+impl LayoutGroup {
+    /// Start laying out `node`, returning its duration if it's a leaf.
+    fn enter(
+        node: Entity,
+        offset: f32,
+        speed: f32,
+        nodes: &AnimationNodes,
+        spans: &mut Vec<LeafSpan>,
+        visited: &mut HashSet<Entity>,
+    ) -> Result<f32, Self> {
+        if !visited.insert(node) {
+            report_cycle(node);
+            return Ok(0.0);
+        }
+
+        let Ok((children, animation, duration, node_speed, ..)) = nodes.get(node) else {
+            return Ok(0.0);
+        };
+
+        // Other speeds would break the ordering the sweep relies on.
+        let node_speed = match node_speed.map_or(1.0, |s| s.0) {
+            s if s > 0.0 && s.is_finite() => s,
+            s => {
+                warn!("animation node {node} has a speed of {s}, which will be ignored");
+                1.0
+            }
+        };
+        let speed = speed * node_speed;
+
+        if children.is_some() && animation == Some(&Animation::Leaf) {
+            warn!("animation leaf {node} has children, which will be ignored");
+        }
+
+        match leaf_children(children, animation) {
+            None => {
+                let length = duration.map(|d| d.0.as_secs_f32()).unwrap_or_default();
+                let duration = length / speed;
+                spans.push(LeafSpan {
+                    entity: node,
+                    start: offset,
+                    duration,
+                    speed,
+                    length,
+                    reach: offset + duration,
+                });
+
+                Ok(duration)
+            }
+            Some(children) => Err(Self {
+                start: offset,
+                speed,
+                parallel: matches!(animation, Some(Animation::Parallel)),
+                children: followers(children, nodes).collect(),
+                next: 0,
+                delay: 0.0,
+                extent: 0.0,
+            }),
+        }
+    }
+
+    /// Account for a child that took `duration` on the timeline.
+    fn finish_child(&mut self, duration: f32) {
+        if self.parallel {
+            self.extent = self.extent.max(self.delay + duration);
+        } else {
+            self.extent += duration;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keyframe, StartOffset, animations, drivers::TimeDriver, lens, testing::app};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn only_roots_are_indexed_with_their_progress() {
+        let mut app = app(0.25);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                TimeDriver::default(),
+                animations![
+                    (Keyframe(Vec3::X), AnimationDuration::secs(1.0)),
+                    (Keyframe(Vec3::Y), AnimationDuration::secs(1.0)),
+                ],
+            ))
+            .id();
+
+        app.update();
+        app.update();
+
+        let index = app.world().resource::<AnimationTargetIndex>();
+        assert_eq!(index.playheads(target).collect::<Vec<_>>(), [root]);
+
+        let progress = app
+            .world_mut()
+            .run_system_cached_with(
+                |In(target): In<Entity>, animations: AnimationsTargeting| {
+                    animations.progress(target).collect::<Vec<_>>()
+                },
+                target,
+            )
+            .unwrap();
+        let elapsed = app.world().get::<AnimationPlayhead>(root).unwrap().get();
+        assert!(elapsed > 0.0);
+        assert_eq!(progress, [(root, elapsed / 2.0)]);
+    }
+
+    fn leaf(index: u32) -> LeafSpan {
+        let start = index as f32;
+        LeafSpan {
+            entity: Entity::from_raw(index),
+            start,
+            duration: 1.0,
+            speed: 1.0,
+            length: 1.0,
+            reach: start + 1.0,
+        }
+    }
+
+    #[test]
+    fn every_step_leaving_the_start_is_flagged() {
+        let layout = SequenceLayout {
+            spans: (0..3).map(leaf).collect(),
+            total: 3.0,
+        };
+        let mut steps = PlayheadSteps::default();
+        let mut playhead = AnimationPlayhead::default();
+
+        playhead.seek_to(3.0);
+        playhead.sweep(Entity::PLACEHOLDER, &layout, &mut steps);
+
+        let flags: Vec<_> = (0..3)
+            .flat_map(|stage| &steps.stages[&stage])
+            .map(|step| (step.start, step.end))
+            .collect();
+        assert_eq!(flags, [(true, false), (true, false), (true, true)]);
+
+        steps.stages.clear();
+        playhead.seek_to(1.5);
+        playhead.sweep(Entity::PLACEHOLDER, &layout, &mut steps);
+
+        let flags: Vec<_> = (0..2)
+            .flat_map(|stage| &steps.stages[&stage])
+            .map(|step| (step.start, step.end))
+            .collect();
+        assert_eq!(flags, [(true, false), (true, false)]);
+    }
+
+    #[test]
+    fn steps_apply_in_the_same_order_regardless_of_query_order() {
+        #[derive(Component)]
+        struct Marker;
+
+        #[derive(Resource, Default)]
+        struct Started(Vec<Entity>);
+
+        let mut app = app(0.1);
+        app.init_resource::<Started>().add_observer(
+            |trigger: Trigger<SequenceEvent>, mut started: ResMut<Started>| {
+                if *trigger == SequenceEvent::SequenceStarted {
+                    started.0.push(trigger.target());
                 }
+            },
+        );
 
-                // now manage swept leaves in reverse direction
-                for (step, (first_leaf, previous_position, start, end, leaf)) in
-                    swept_leaves.into_iter().rev().enumerate()
-                {
-                    let started = previous_position >= time;
-                    let ended = playhead_instant <= 0.0 && first_leaf;
-
-                    steps.0.entry(step).or_default().push(PlayheadStep {
-                        playhead: playhead_entity,
-                        start: started,
-                        end: ended,
-                        entity: leaf,
-                        movement: PlayheadMove { start, end },
-                    });
+        // Alternating archetypes makes the driver query
+        // visit the playheads out of spawn order.
+        let playheads: Vec<_> = (0..8)
+            .map(|i| {
+                let mut playhead = app
+                    .world_mut()
+                    .spawn((TimeDriver::default(), animations![crate::Delay::secs(1.0)]));
+                if i % 2 == 0 {
+                    playhead.insert(Marker);
                 }
-            }
+                playhead.id()
+            })
+            .collect();
+
+        app.update();
+        app.update();
+
+        let mut sorted = playheads.clone();
+        sorted.sort();
+        assert_eq!(app.world().resource::<Started>().0, sorted);
+    }
+
+    #[test]
+    fn nested_speeds_multiply() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                AnimationSpeed(2.0),
+                animations![(
+                    AnimationSpeed(2.0),
+                    animations![(Keyframe(Vec3::X), AnimationDuration::secs(1.0))],
+                )],
+            ))
+            .id();
+
+        // Register the node's systems, then let it propagate its lens.
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().get(root), Some(&SequenceDuration(0.25)));
+
+        step_animation(app.world_mut(), root, 0.125).unwrap();
+        let translation = app.world().get::<Transform>(target).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::X * 0.5, 1e-5));
+    }
+
+    #[test]
+    fn speeds_that_arent_positive_are_ignored() {
+        let mut app = app(0.1);
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationSpeed(0.0),
+                animations![
+                    (AnimationSpeed(-2.0), AnimationDuration::secs(1.0)),
+                    (AnimationSpeed(f32::NAN), AnimationDuration::secs(1.0)),
+                ],
+            ))
+            .id();
+        app.update();
+
+        assert_eq!(app.world().get(root), Some(&SequenceDuration(2.0)));
+    }
+
+    #[test]
+    fn parallel_children_start_together_and_end_on_their_own() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let root = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                Animation::Parallel,
+                animations![
+                    (
+                        lens!(Transform::translation),
+                        Keyframe(Vec3::X),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Transform::scale),
+                        Keyframe(Vec3::splat(3.0)),
+                        AnimationDuration::secs(2.0),
+                    ),
+                ],
+            ))
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        // The group lasts as long as its longest child.
+        assert_eq!(app.world().get(root), Some(&SequenceDuration(2.0)));
+
+        let transform = |app: &bevy_app::App| *app.world().get::<Transform>(target).unwrap();
+        for (delta, translation, scale) in [
+            (0.5, Vec3::X * 0.5, 1.5),
+            (0.5, Vec3::X, 2.0),
+            (1.0, Vec3::X, 3.0),
+        ] {
+            step_animation(app.world_mut(), root, delta).unwrap();
+            let transform = transform(&app);
+            assert!(transform.translation.abs_diff_eq(translation, 1e-5));
+            assert!(transform.scale.abs_diff_eq(Vec3::splat(scale), 1e-5));
         }
+    }
 
-        Ok(())
+    #[test]
+    fn offsets_delay_parallel_children_only() {
+        let mut app = app(0.1);
+        let target = app.world_mut().spawn(Transform::default()).id();
+        let parallel = app
+            .world_mut()
+            .spawn((
+                AnimationTarget(target),
+                Animation::Parallel,
+                animations![
+                    (
+                        lens!(Transform::translation),
+                        Keyframe(Vec3::X),
+                        AnimationDuration::secs(1.0),
+                    ),
+                    (
+                        lens!(Transform::scale),
+                        Keyframe(Vec3::splat(3.0)),
+                        AnimationDuration::secs(1.0),
+                        StartOffset::secs(0.5),
+                    ),
+                ],
+            ))
+            .id();
+        let sequence = app
+            .world_mut()
+            .spawn(animations![
+                AnimationDuration::secs(1.0),
+                (AnimationDuration::secs(1.0), StartOffset::secs(0.5)),
+            ])
+            .id();
+
+        // Register the nodes' systems, then let them propagate their lenses.
+        app.update();
+        app.update();
+
+        // The offset child ends last, while the sequence ignores the offset.
+        assert_eq!(app.world().get(parallel), Some(&SequenceDuration(1.5)));
+        assert_eq!(app.world().get(sequence), Some(&SequenceDuration(2.0)));
+
+        let transform = |app: &bevy_app::App| *app.world().get::<Transform>(target).unwrap();
+        for (delta, translation, scale) in [
+            (0.25, Vec3::X * 0.25, 1.0),
+            (0.75, Vec3::X, 2.0),
+            (0.5, Vec3::X, 3.0),
+        ] {
+            step_animation(app.world_mut(), parallel, delta).unwrap();
+            let transform = transform(&app);
+            assert!(transform.translation.abs_diff_eq(translation, 1e-5));
+            assert!(transform.scale.abs_diff_eq(Vec3::splat(scale), 1e-5));
+        }
     }
 }
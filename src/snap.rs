@@ -0,0 +1,145 @@
+use super::{AnimationCurve, AnimationDuration, AnimationTarget, get_time, playhead::PlayheadMove};
+use bevy_ecs::{component::Mutable, prelude::*};
+use core::marker::PhantomData;
+
+/// Snap a field to a value partway through the node.
+///
+/// Unlike [`Keyframe`](crate::Keyframe), the field doesn't need to implement
+/// [`AnimationLerp`](crate::AnimationLerp), so this can drive discrete states
+/// like booleans or enums. The field holds its starting value until the node's
+/// time passes the threshold, `0.5` by default, and then takes the new value.
+/// Moving back before the threshold restores the starting value, which is
+/// captured when the field is first snapped.
+///
+/// ```ignore
+/// (
+///     Snap::new(|visibility: &mut Visibility| visibility, Visibility::Hidden),
+///     AnimationDuration::secs(1.0),
+/// )
+/// ```
+#[derive(Component)]
+#[require(AnimationDuration)]
+pub struct Snap {
+    field: Option<Box<dyn SnapField>>,
+    threshold: f32,
+    snapped: bool,
+}
+
+impl Snap {
+    pub fn new<C, T, F>(field: F, value: T) -> Self
+    where
+        C: Component<Mutability = Mutable>,
+        T: Clone + PartialEq + Send + Sync + 'static,
+        F: Fn(&mut C) -> &mut T + Send + Sync + 'static,
+    {
+        Self {
+            field: Some(Box::new(FunctionSnapField {
+                field,
+                value,
+                start: None,
+                _marker: PhantomData,
+            })),
+            threshold: 0.5,
+            snapped: false,
+        }
+    }
+
+    /// Set the fraction of the node's time at which the value snaps.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub(super) fn handle_movement(
+        mut snaps: Query<
+            (
+                Entity,
+                &mut Self,
+                &AnimationDuration,
+                &AnimationTarget,
+                &PlayheadMove,
+                Option<&AnimationCurve>,
+            ),
+            Changed<PlayheadMove>,
+        >,
+        mut commands: Commands,
+    ) {
+        for (entity, mut snap, duration, target, movement, curve) in &mut snaps {
+            let t = get_time(duration.0, movement.end, curve);
+            let snapped = t >= snap.threshold;
+
+            // Like `Keyframe`, we re-capture the start when moving
+            // forward from the very beginning.
+            let just_started = movement.enters_from_start();
+            if snapped == snap.snapped && !just_started {
+                continue;
+            }
+            snap.snapped = snapped;
+
+            let target = target.0;
+            commands.queue(move |world: &mut World| -> Result {
+                let mut field = world
+                    .get_mut::<Self>(entity)
+                    .and_then(|mut snap| snap.field.take())
+                    .ok_or_else(|| format!("snap node {entity} is missing its field"))?;
+
+                let result = world
+                    .get_entity_mut(target)
+                    .map_err(Into::into)
+                    .and_then(|mut target| field.apply(&mut target, snapped, just_started));
+
+                if let Some(mut snap) = world.get_mut::<Self>(entity) {
+                    snap.field = Some(field);
+                }
+
+                result
+            });
+        }
+    }
+}
+
+trait SnapField: Send + Sync + 'static {
+    /// Set the field to the snapped value, or restore its start.
+    fn apply(&mut self, target: &mut EntityWorldMut, snapped: bool, recapture: bool) -> Result;
+}
+
+struct FunctionSnapField<C, T, F> {
+    field: F,
+    value: T,
+    start: Option<T>,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C, T, F> SnapField for FunctionSnapField<C, T, F>
+where
+    C: Component<Mutability = Mutable>,
+    T: Clone + PartialEq + Send + Sync + 'static,
+    F: Fn(&mut C) -> &mut T + Send + Sync + 'static,
+{
+    fn apply(&mut self, target: &mut EntityWorldMut, snapped: bool, recapture: bool) -> Result {
+        let id = target.id();
+        let mut component = target
+            .get_mut::<C>()
+            .ok_or_else(|| format!("snap target {id} has no `{}`", core::any::type_name::<C>()))?;
+
+        // Avoid triggering change detection when the value is already in place.
+        let field = (self.field)(component.bypass_change_detection());
+
+        if recapture || self.start.is_none() {
+            self.start = Some(field.clone());
+        }
+
+        let value = match (snapped, &self.start) {
+            (true, _) => &self.value,
+            (false, Some(start)) => start,
+            (false, None) => return Ok(()),
+        };
+
+        if field != value {
+            *field = value.clone();
+            component.set_changed();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use super::{AnimationDuration, Animations, CapturedInterval, Keyframe, lerp::AnimationLerp};
+use bevy_ecs::{prelude::*, system::SystemParam};
+
+/// Interpolate a sequence's [`Keyframe`]s along a smooth spline.
+///
+/// Normally, each keyframe eases towards its value independently, so motion
+/// can change speed abruptly at node boundaries. Inside a sequence with this
+/// component, consecutive keyframes of the same type share Catmull-Rom
+/// tangents, keeping velocity continuous from one node to the next.
+///
+/// The tangents are weighted by each node's duration, so nodes of different
+/// lengths still join smoothly. The first and last keyframes use their own
+/// interval as the missing neighbor. A node's
+/// [`AnimationCurve`](crate::AnimationCurve) still remaps its time, so nodes
+/// should generally be left linear.
+///
+/// ```ignore
+/// (
+///     Spline,
+///     lens!(Transform::translation),
+///     animations![
+///         (Keyframe(Vec3::X), AnimationDuration::secs(0.5)),
+///         (Keyframe(Vec3::Y), AnimationDuration::secs(0.5)),
+///         (Keyframe(Vec3::ZERO), AnimationDuration::secs(1.0)),
+///     ],
+/// )
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Spline;
+
+/// The siblings of a node in a [`Spline`] sequence.
+///
+/// This is cached on each child, so keyframes can find their
+/// neighbors without walking the hierarchy while they animate.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SplineNeighbors {
+    previous: Option<Entity>,
+    next: Option<Entity>,
+}
+
+impl Spline {
+    pub(super) fn update_neighbors(
+        splines: Query<&Animations, (With<Self>, Or<(Changed<Animations>, Added<Self>)>)>,
+        mut commands: Commands,
+    ) {
+        for children in &splines {
+            let children: Vec<_> = children.iter().collect();
+
+            for (i, child) in children.iter().enumerate() {
+                commands.entity(*child).insert(SplineNeighbors {
+                    previous: i.checked_sub(1).map(|i| children[i]),
+                    next: children.get(i + 1).copied(),
+                });
+            }
+        }
+    }
+}
+
+/// The neighboring keyframes of nodes in a [`Spline`] sequence.
+#[derive(SystemParam)]
+pub(crate) struct SplineKeyframes<'w, 's, T: AnimationLerp> {
+    keyframes: Query<
+        'w,
+        's,
+        (
+            &'static Keyframe<T>,
+            &'static AnimationDuration,
+            Option<&'static CapturedInterval<T>>,
+        ),
+    >,
+}
+
+impl<T: AnimationLerp> SplineKeyframes<'_, '_, T> {
+    /// Sample the spline between `start` and `end` at `t`, from `0.0` to `1.0`.
+    pub fn sample(
+        &self,
+        neighbors: &SplineNeighbors,
+        start: &T,
+        end: &T,
+        duration: &AnimationDuration,
+        t: f32,
+    ) -> T {
+        let duration = duration.0.as_secs_f32();
+
+        // A missing neighbor mirrors this node's own interval.
+        let (before, before_duration) = neighbors
+            .previous
+            .and_then(|previous| self.keyframes.get(previous).ok())
+            .and_then(|(_, d, captured)| Some((&captured?.0.start, d.0.as_secs_f32())))
+            .unwrap_or((start, duration));
+        let (after, after_duration) = neighbors
+            .next
+            .and_then(|next| self.keyframes.get(next).ok())
+            .map(|(keyframe, d, _)| (&keyframe.0, d.0.as_secs_f32()))
+            .unwrap_or((end, duration));
+
+        let start_tangent = tangent(before, end, before_duration + duration, duration);
+        let end_tangent = tangent(start, after, duration + after_duration, duration);
+
+        // The cubic Hermite basis, relative to `start`.
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let mut value = start.clone();
+        value.accumulate(&end.difference(start).scale(3.0 * t2 - 2.0 * t3));
+        value.accumulate(&start_tangent.scale(t3 - 2.0 * t2 + t));
+        value.accumulate(&end_tangent.scale(t3 - t2));
+
+        value
+    }
+}
+
+/// The tangent at a point `span` seconds between `before` and `after`,
+/// scaled to the `segment` being interpolated.
+fn tangent<T: AnimationLerp>(before: &T, after: &T, span: f32, segment: f32) -> T {
+    if span <= 0.0 {
+        return T::identity();
+    }
+
+    after.difference(before).scale(segment / span)
+}
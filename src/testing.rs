@@ -0,0 +1,119 @@
+//! Helpers for testing animations.
+//!
+//! ```ignore
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, KeyframePlugin));
+//!
+//! let target = app.world_mut().spawn(Transform::default()).id();
+//! let playhead = app
+//!     .world_mut()
+//!     .spawn((
+//!         AnimationTarget(target),
+//!         lens!(Transform::translation),
+//!         animations![(Keyframe(Vec3::X), AnimationDuration::secs(1.0))],
+//!     ))
+//!     .id();
+//!
+//! let lens = lens!(Transform::translation);
+//! let value = value_at(app.world_mut(), playhead, target, &lens, 0.5)?;
+//! assert!(value.abs_diff_eq(Vec3::X * 0.5, 1e-4));
+//! ```
+
+use super::{
+    blend::{BlendBuffer, resolve_blends},
+    default_animation_target, dynamic_systems,
+    lens::{FieldAccess, FieldGetter, FieldLens, propagate_lens_ref},
+    lerp::AnimationLerp,
+    playhead::{AnimationPlayhead, step_animation},
+    propagate_animation_target,
+};
+use bevy_ecs::prelude::*;
+
+/// Move `playhead` to `time` seconds and return the field `lens` reads from `target`.
+///
+/// Everything between the playhead's current position and `time` is
+/// evaluated, as with [`AnimationPlayhead::seek_to`], so calls can step
+/// through an animation in order. Animations spawned since the last call,
+/// or since the app last updated, are set up first, so this doesn't need
+/// the app to update in between.
+///
+/// The playhead's driver isn't involved, so it doesn't need one.
+pub fn value_at<T: AnimationLerp>(
+    world: &mut World,
+    playhead: Entity,
+    target: Entity,
+    lens: &impl FieldLens<T>,
+    time: f32,
+) -> Result<T> {
+    world.run_system_cached(default_animation_target)?;
+    world.run_system_cached(propagate_animation_target)??;
+    world.run_system_cached(propagate_lens_ref::<T>)??;
+    world.run_system_cached(dynamic_systems::handle_insertions)?;
+
+    let current = world
+        .get::<AnimationPlayhead>(playhead)
+        .ok_or_else(|| format!("{playhead} has no `AnimationPlayhead`"))?
+        .get();
+    step_animation(world, playhead, time - current)?;
+
+    if world.contains_resource::<BlendBuffer<T>>() {
+        world.run_system_cached(resolve_blends::<T>)??;
+    }
+
+    let mut query = world.query::<FieldGetter<T>>();
+    let entity = query.get_mut(world, target)?;
+    lens.get_field(FieldAccess::new(entity))
+}
+
+/// An app whose clock advances by `step` seconds every update.
+#[cfg(test)]
+pub(crate) fn app(step: f32) -> bevy_app::App {
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+
+    let mut app = bevy_app::App::new();
+    app.add_plugins((TimePlugin, crate::KeyframePlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(
+            core::time::Duration::from_secs_f32(step),
+        ));
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnimationDuration, AnimationTarget, Delta, Keyframe, Shift, animations, lens};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn value_at_steps_through_each_node() {
+        let mut app = app(0.1);
+        let world = app.world_mut();
+
+        let target = world.spawn(Transform::default()).id();
+        let playhead = world
+            .spawn((
+                AnimationTarget(target),
+                lens!(Transform::translation),
+                animations![
+                    (Keyframe(Vec3::X * 2.0), AnimationDuration::secs(1.0)),
+                    (Shift(Vec3::Y), AnimationDuration::secs(1.0)),
+                    (Delta(Vec3::Z), AnimationDuration::secs(1.0)),
+                ],
+            ))
+            .id();
+
+        let lens = lens!(Transform::translation);
+        for (time, expected) in [
+            (0.5, Vec3::X),
+            (1.0, Vec3::X * 2.0),
+            (1.5, Vec3::new(2.0, 0.5, 0.0)),
+            (2.0, Vec3::new(2.0, 1.0, 0.0)),
+            (2.5, Vec3::new(2.0, 1.0, 0.5)),
+            (3.0, Vec3::new(2.0, 1.0, 1.0)),
+        ] {
+            let value = value_at(world, playhead, target, &lens, time).unwrap();
+            assert!(value.abs_diff_eq(expected, 1e-5), "{value} at {time}s");
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "derive")]
+
+use bevy_keyframe::AnimationLerp;
+use bevy_math::{Vec2, Vec3};
+
+#[derive(AnimationLerp, Clone, Debug, PartialEq)]
+struct Sprite {
+    position: Vec3,
+    alpha: f32,
+}
+
+#[derive(AnimationLerp, Clone, Debug, PartialEq)]
+struct Offset(Vec2, f32);
+
+#[test]
+fn named_structs_animate_field_wise() {
+    let start = Sprite {
+        position: Vec3::ZERO,
+        alpha: 1.0,
+    };
+    let end = Sprite {
+        position: Vec3::new(2.0, 4.0, -2.0),
+        alpha: 0.0,
+    };
+
+    assert_eq!(
+        start.animation_lerp(&end, 0.5),
+        Sprite {
+            position: Vec3::new(1.0, 2.0, -1.0),
+            alpha: 0.5,
+        }
+    );
+
+    let mut out = Sprite::identity();
+    start.animation_lerp_into(&end, 0.25, &mut out);
+    assert_eq!(out, start.animation_lerp(&end, 0.25));
+
+    let difference = end.difference(&start);
+    assert_eq!(
+        difference,
+        Sprite {
+            position: Vec3::new(2.0, 4.0, -2.0),
+            alpha: -1.0,
+        }
+    );
+
+    let mut accumulated = start.clone();
+    accumulated.accumulate(&difference);
+    assert_eq!(accumulated, end);
+}
+
+#[test]
+fn tuple_structs_animate_field_wise() {
+    let start = Offset(Vec2::new(1.0, 1.0), 2.0);
+    let end = Offset(Vec2::new(3.0, -1.0), 6.0);
+
+    assert_eq!(Offset::identity(), Offset(Vec2::ZERO, 0.0));
+    assert_eq!(
+        start.animation_lerp(&end, 0.5),
+        Offset(Vec2::new(2.0, 0.0), 4.0)
+    );
+
+    let difference = end.difference(&start);
+    assert_eq!(difference, Offset(Vec2::new(2.0, -2.0), 4.0));
+
+    let mut accumulated = start.clone();
+    accumulated.accumulate(&difference);
+    assert_eq!(accumulated, end);
+}
+
+#[test]
+fn enums_cant_be_derived() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
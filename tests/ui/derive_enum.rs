@@ -0,0 +1,9 @@
+use bevy_keyframe::AnimationLerp;
+
+#[derive(AnimationLerp, Clone)]
+enum Fade {
+    In(f32),
+    Out(f32),
+}
+
+fn main() {}